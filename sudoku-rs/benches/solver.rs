@@ -0,0 +1,50 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use sudoku_rs::{
+    grid::Grid,
+    solver::{bitboard::BitboardSolver, brute_force::BruteForceSolver},
+};
+
+const PUZZLES: [&str; 4] = [
+    "040000200070205849285409300031000920000070000052000470007908632328501090004000010",
+    "...16.87..1.875..38.73..651.5.62173...17..5.473.5..1...7........8.256917.62..7...",
+    "...481.5.3......9.1...7...47....3.6...65....3....9...8....3...2....57....7....8.9",
+    "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..",
+];
+
+fn bench_solution_state(c: &mut Criterion) {
+    let grids: Vec<Grid> = PUZZLES
+        .iter()
+        .map(|s| Grid::new_from_singline_digit(s).unwrap())
+        .collect();
+    let mut group = c.benchmark_group("solution_state");
+    group.bench_function("brute_force", |b| {
+        let solver = BruteForceSolver::new();
+        b.iter(|| {
+            for grid in grids.iter() {
+                black_box(solver.get_solution_state(grid));
+            }
+        })
+    });
+    group.bench_function("bitboard", |b| {
+        let solver = BitboardSolver::new();
+        b.iter(|| {
+            for grid in grids.iter() {
+                black_box(solver.get_solution_state(grid));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_count_solutions(c: &mut Criterion) {
+    // only the first four rows are given
+    let s = format!("{}{}", &PUZZLES[1][..36], ".".repeat(45));
+    let grid = Grid::new_from_singline_digit(&s).unwrap();
+    let solver = BitboardSolver::new();
+    c.bench_function("count_solutions_1000", |b| {
+        b.iter(|| black_box(solver.count_solutions(&grid, 1000)))
+    });
+}
+
+criterion_group!(benches, bench_solution_state, bench_count_solutions);
+criterion_main!(benches);
@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, PartialEq, Hash, Eq, Copy)]
+#[derive(Debug, Clone, PartialEq, Hash, Eq, Copy, PartialOrd, Ord)]
 pub struct Candidate(u8, u8);
 
 impl Candidate {
@@ -1,13 +1,45 @@
 use thiserror::Error;
 
 #[derive(Error, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SudokuError {
     #[error("input sudoku `{0}` is invalid")]
     InvalidInput(String),
     #[error("grid state error: `{0}`")]
     GridStateError(String),
-    #[error("generate faild")]
-    GenerateFailed,
+    #[error("generate faild: {0}")]
+    GenerateFailed(GenerateFailReason),
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum GenerateFailReason {
+    #[error("invalid generator config: `{0}`")]
+    InvalidConfig(String),
+    // counts of the rejected puzzles by reason
+    #[error(
+        "no puzzle matched after {attempts} attempts, too easy: {too_easy}, too hard: {too_hard}, missing technique: {missing_technique}, forbidden technique: {forbidden_technique}, clue count missed: {clue_count_missed}"
+    )]
+    MaxAttempts {
+        attempts: usize,
+        too_easy: usize,
+        too_hard: usize,
+        missing_technique: usize,
+        forbidden_technique: usize,
+        clue_count_missed: usize,
+    },
+}
+
+impl SudokuError {
+    // an error in the input text, line and column start from 1
+    pub fn invalid_at(line: usize, column: usize, reason: impl Into<String>) -> Self {
+        SudokuError::InvalidInput(format!(
+            "line {}, column {}: {}",
+            line,
+            column,
+            reason.into()
+        ))
+    }
 }
 
 pub type Result<T> = std::result::Result<T, SudokuError>;
@@ -1,101 +1,236 @@
 use crate::{
-    error::{Result, SudokuError},
+    error::{GenerateFailReason, Result, SudokuError},
+    generator::symmetry::Symmetry,
     grid::{Difficulty, Grid},
     solution::SolutionState,
-    solver::{SimpleSolver, brute_force::BruteForceSolver},
+    solver::{SimpleSolver, bitboard::BitboardSolver, config::SolverConfig},
 };
 
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 pub struct GeneratedGrid {
     pub grid: Grid,
     pub solution: [u8; 81],
     pub score: u32,
 }
+
+#[derive(Debug, Clone)]
+pub struct GeneratorConfig {
+    // the score of the SolutionPath must be in min_score..=max_score
+    pub min_score: u32,
+    pub max_score: u32,
+    pub min_clue: u8,
+    pub max_clue: u8,
+    pub symmetry: Symmetry,
+    // step names like "X-Wing", all of them must be used by the solution path
+    pub required_techniques: Vec<String>,
+    // step names which can't be used by the solution path
+    pub forbidden_techniques: Vec<String>,
+    pub max_attempts: usize,
+    // the solver which rates the puzzles, every attempt is solved by it
+    pub solver: SolverConfig,
+    // the same seed generates the same puzzle
+    pub seed: Option<u64>,
+}
+
+impl GeneratorConfig {
+    pub fn new(difficulty: &Difficulty) -> Self {
+        GeneratorConfig {
+            min_score: difficulty.min_score(),
+            max_score: difficulty.max_score(),
+            min_clue: difficulty.min_clue(),
+            max_clue: difficulty.max_clue(),
+            symmetry: Symmetry::None,
+            required_techniques: Vec::new(),
+            forbidden_techniques: Vec::new(),
+            max_attempts: 100,
+            solver: SolverConfig::rating(),
+            seed: None,
+        }
+    }
+
+    fn check(&self) -> Result<()> {
+        let reason = if self.min_score > self.max_score {
+            Some("min_score is bigger than max_score".to_string())
+        } else if self.min_clue > self.max_clue || self.min_clue < 17 || self.max_clue > 81 {
+            Some(format!(
+                "clue range {}..={} is invalid",
+                self.min_clue, self.max_clue
+            ))
+        } else {
+            self.required_techniques
+                .iter()
+                .find(|t| self.forbidden_techniques.contains(t))
+                .map(|t| format!("technique {} is required and forbidden", t))
+        };
+        match reason {
+            Some(reason) => Err(SudokuError::GenerateFailed(
+                GenerateFailReason::InvalidConfig(reason),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
 pub fn generate_sudoku(difficulty: &Difficulty) -> Result<GeneratedGrid> {
-    let solver = BruteForceSolver::new();
-    let simple_solver = SimpleSolver::new();
-    while let Some(solution_grid) = solver.generate_solution() {
-        let rand_cells = generate_cell_order();
-        let mut attempts = 0;
-        let mut rng = rand::rng();
-        let expect_clude_num = rng.random_range(difficulty.min_clue()..difficulty.max_clue());
-        let mut grid = solution_grid.clone();
-        let mut index = rng.random_range(0..81);
-        attempts += 1;
-        if attempts > 1000 {
-            break;
+    generate_with_config(&GeneratorConfig::new(difficulty))
+}
+
+pub fn generate_symmetric_sudoku(
+    difficulty: &Difficulty,
+    symmetry: &Symmetry,
+) -> Result<GeneratedGrid> {
+    let mut config = GeneratorConfig::new(difficulty);
+    config.symmetry = *symmetry;
+    generate_with_config(&config)
+}
+
+// the puzzle of the seed, ChaCha8 is portable so it is the same on every platform and release
+pub fn generate_sudoku_with_seed(difficulty: &Difficulty, seed: u64) -> Result<GeneratedGrid> {
+    let mut config = GeneratorConfig::new(difficulty);
+    config.seed = Some(seed);
+    generate_with_config(&config)
+}
+
+pub fn generate_with_config(config: &GeneratorConfig) -> Result<GeneratedGrid> {
+    let mut rng = match config.seed {
+        Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+        None => ChaCha8Rng::from_rng(&mut rand::rng()),
+    };
+    generate_with_rng(config, &mut rng)
+}
+
+// every puzzle is rated by the solver of the config, only the one matches the config is returned.
+// all randomness comes from rng, the seed of the config is not used
+pub fn generate_with_rng<R: Rng>(config: &GeneratorConfig, rng: &mut R) -> Result<GeneratedGrid> {
+    config.check()?;
+    let solver = BitboardSolver::new();
+    let simple_solver = SimpleSolver::from_config(&config.solver)?;
+    let mut too_easy = 0;
+    let mut too_hard = 0;
+    let mut missing_technique = 0;
+    let mut forbidden_technique = 0;
+    let mut clue_count_missed = 0;
+    for _ in 0..config.max_attempts {
+        let solution_grid = solver.generate_solution_with_rng(rng);
+        let expect_clude_num = rng.random_range(config.min_clue..=config.max_clue);
+        let Some(grid) = remove_clues(&solver, &solution_grid, expect_clude_num, config, rng)
+        else {
+            clue_count_missed += 1;
+            continue;
+        };
+        // the clues are givens, uniqueness steps like avoidable rectangles depend on it
+        let grid = Grid::new_from_singline_digit(&grid.to_digit_line())?;
+        let mut grid_to_solve = grid.clone();
+        let solution = simple_solver.solve(&mut grid_to_solve);
+        let names: Vec<&str> = solution.steps().iter().map(|step| step.name()).collect();
+        if !grid_to_solve.is_solved() {
+            too_hard += 1;
+        } else if solution.score() < config.min_score {
+            too_easy += 1;
+        } else if solution.score() > config.max_score {
+            too_hard += 1;
+        } else if config
+            .required_techniques
+            .iter()
+            .any(|t| !names.contains(&t.as_str()))
+        {
+            missing_technique += 1;
+        } else if config
+            .forbidden_techniques
+            .iter()
+            .any(|t| names.contains(&t.as_str()))
+        {
+            forbidden_technique += 1;
+        } else {
+            return Ok(GeneratedGrid {
+                grid,
+                solution: solution_grid.values().to_owned(),
+                score: solution.score(),
+            });
         }
-        let mut count_down = 162;
-        let mut remain_clues = 81;
-        while remain_clues >= 17 && count_down > 0 {
-            let cell = rand_cells[index];
-            if grid.get_value(cell) != 0 {
-                let set_success = grid.set_value(cell, 0, false);
-                count_down -= 1;
-                if set_success {
-                    remain_clues -= 1;
+    }
+    Err(SudokuError::GenerateFailed(
+        GenerateFailReason::MaxAttempts {
+            attempts: config.max_attempts,
+            too_easy,
+            too_hard,
+            missing_technique,
+            forbidden_technique,
+            clue_count_missed,
+        },
+    ))
+}
 
-                    let state = solver.get_solution_state(&grid);
-                    match state {
-                        SolutionState::NoSolution => {
-                            panic!("imposiabble no solution when generate");
-                        }
-                        SolutionState::Unique => {
-                            count_down -= 1;
-                            let mut grid_to_solve = grid.clone();
-                            let solution = simple_solver.solve(&mut grid_to_solve);
-                            if remain_clues == expect_clude_num {
-                                let res = GeneratedGrid {
-                                    grid: grid,
-                                    solution: solution_grid.values().to_owned(),
-                                    score: solution.score(),
-                                };
-                                return Ok(res);
-                            }
-                            continue;
+// remove clues from the solution in random order until expect_clude_num clues are left, clues
+// are removed together with their images under the symmetry and the puzzle is kept unique. an
+// orbit is only removed if min_clue clues are left, so the clues are always in the clue range
+// of the config
+fn remove_clues<R: Rng>(
+    solver: &BitboardSolver,
+    solution_grid: &Grid,
+    expect_clude_num: u8,
+    config: &GeneratorConfig,
+    rng: &mut R,
+) -> Option<Grid> {
+    let rand_cells = generate_cell_order(rng);
+    let mut grid = solution_grid.clone();
+    let mut index = rng.random_range(0..81);
+    let mut count_down = 162;
+    let mut remain_clues = 81;
+    while remain_clues >= 17 && count_down > 0 {
+        let cell = rand_cells[index];
+        if grid.get_value(cell) != 0 {
+            let orbit: Vec<u8> = config
+                .symmetry
+                .orbit(cell)
+                .into_iter()
+                .filter(|c| grid.get_value(*c) != 0)
+                .collect();
+            if remain_clues < config.min_clue + orbit.len() as u8 {
+                count_down -= 1;
+                index = (index + 1) % 81;
+                continue;
+            }
+            let set_success = orbit.iter().all(|c| grid.set_value(*c, 0, false));
+            count_down -= 1;
+            if set_success {
+                remain_clues -= orbit.len() as u8;
+                match solver.get_solution_state(&grid) {
+                    SolutionState::NoSolution => {
+                        panic!("imposiabble no solution when generate");
+                    }
+                    SolutionState::Unique => {
+                        count_down -= 1;
+                        if remain_clues <= expect_clude_num {
+                            return Some(grid);
                         }
-                        SolutionState::MoreThanOne => {
-                            remain_clues += 1;
-                            grid.set_value(cell, solution_grid.get_value(cell), false);
+                    }
+                    SolutionState::MoreThanOne => {
+                        remain_clues += orbit.len() as u8;
+                        for c in orbit.iter() {
+                            grid.set_value(*c, solution_grid.get_value(*c), false);
                         }
                     }
-                } else {
-                    println!("set value failed");
                 }
-            }
-            index = (index + 1) % 81;
-        }
-        let mut grid_to_solve = grid.clone();
-        let state = solver.get_solution_state(&grid_to_solve);
-        match state {
-            SolutionState::Unique => {
-                let solution = simple_solver.solve(&mut grid_to_solve);
-
-                if remain_clues == expect_clude_num {
-                    let res = GeneratedGrid {
-                        grid: grid,
-                        solution: solution_grid.values().to_owned(),
-                        score: solution.score(),
-                    };
-                    return Ok(res);
+            } else {
+                for c in orbit.iter() {
+                    grid.set_value(*c, solution_grid.get_value(*c), false);
                 }
             }
-            _ => {
-                return Err(SudokuError::GenerateFailed);
-            }
         }
+        index = (index + 1) % 81;
     }
-    return Err(SudokuError::GenerateFailed);
+    (remain_clues <= config.max_clue).then_some(grid)
 }
 
-fn generate_cell_order() -> [u8; 81] {
+fn generate_cell_order<R: Rng>(rng: &mut R) -> [u8; 81] {
     let mut cells = [0; 81];
     for i in 0..81 {
         cells[i] = i as u8;
     }
     for _ in 0..81 {
-        let mut rng = rand::rng();
         let a: usize = rng.random_range(0..81);
         let b: usize = rng.random_range(0..81);
         cells.swap(a, b);
@@ -105,8 +240,19 @@ fn generate_cell_order() -> [u8; 81] {
 
 #[cfg(test)]
 mod test {
-    use super::generate_sudoku;
-    use crate::grid::Difficulty;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::{
+        GeneratorConfig, generate_sudoku, generate_sudoku_with_seed, generate_symmetric_sudoku,
+        generate_with_config, generate_with_rng,
+    };
+    use crate::{
+        error::{GenerateFailReason, SudokuError},
+        generator::symmetry::Symmetry,
+        grid::Difficulty,
+        solver::{SimpleSolver, config::SolverConfig},
+    };
 
     #[test]
     pub fn test_generate() {
@@ -122,4 +268,112 @@ mod test {
         }
         panic!("generate failed",);
     }
+
+    #[test]
+    pub fn test_generate_symmetric() {
+        let df = Difficulty::Medium;
+        let symmetry = Symmetry::Rotational180;
+        for _ in 0..10 {
+            if let Ok(generated_grid) = generate_symmetric_sudoku(&df, &symmetry) {
+                let grid = generated_grid.grid;
+                for cell in 0..81 {
+                    let is_clue = grid.get_value(cell) != 0;
+                    for image in symmetry.orbit(cell) {
+                        assert_eq!(grid.get_value(image) != 0, is_clue);
+                    }
+                }
+                return;
+            }
+        }
+        panic!("generate failed",);
+    }
+
+    #[test]
+    pub fn test_generate_clue_range() {
+        let mut config = GeneratorConfig::new(&Difficulty::Hard);
+        config.symmetry = Symmetry::Full;
+        config.min_score = 0;
+        config.max_score = u32::MAX;
+        config.min_clue = 28;
+        config.max_clue = 32;
+        for seed in 0..5 {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let generated = generate_with_rng(&config, &mut rng).unwrap();
+            let clues = generated.grid.clude_count();
+            assert!((28..=32).contains(&clues));
+        }
+
+        // the orbits of the full symmetry have 1, 4 or 8 cells, 30 clues can't be made
+        config.min_clue = 30;
+        config.max_clue = 30;
+        config.max_attempts = 5;
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        match generate_with_rng(&config, &mut rng) {
+            Err(SudokuError::GenerateFailed(GenerateFailReason::MaxAttempts {
+                clue_count_missed,
+                ..
+            })) => assert_eq!(clue_count_missed, 5),
+            _ => panic!("generate should fail"),
+        }
+    }
+
+    #[test]
+    pub fn test_generate_with_seed() {
+        let df = Difficulty::Medium;
+        let generated = generate_sudoku_with_seed(&df, 2).unwrap();
+        // the puzzle of the day is shared by seed, it must not change with the rand release
+        assert_eq!(
+            generated.grid.to_digit_line(),
+            "400106002006032050053940176508461327040283600362009001084325069930007000020090000"
+        );
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        let again = generate_with_rng(&GeneratorConfig::new(&df), &mut rng).unwrap();
+        assert_eq!(generated.grid.values(), again.grid.values());
+        assert_eq!(generated.score, again.score);
+        // the clues are givens and the rating solver solves the puzzle
+        for cell in 0..81 {
+            assert_eq!(
+                generated.grid.cell_is_given(cell),
+                generated.grid.get_value(cell) != 0
+            );
+        }
+        let mut grid = generated.grid.clone();
+        SimpleSolver::from_config(&SolverConfig::rating())
+            .unwrap()
+            .solve(&mut grid);
+        assert_eq!(grid.values(), &generated.solution);
+    }
+
+    #[test]
+    pub fn test_generate_with_config() {
+        let mut config = GeneratorConfig::new(&Difficulty::Easy);
+        config.seed = Some(7);
+        config.forbidden_techniques = vec!["Locked Candidate".to_string()];
+        let generated = generate_with_config(&config).unwrap();
+        assert!(generated.score <= Difficulty::Easy.max_score());
+        let again = generate_with_config(&config).unwrap();
+        assert_eq!(generated.grid.values(), again.grid.values());
+
+        config.required_techniques = vec!["Locked Candidate".to_string()];
+        match generate_with_config(&config) {
+            Err(SudokuError::GenerateFailed(GenerateFailReason::InvalidConfig(_))) => {}
+            _ => panic!("config should be invalid"),
+        }
+
+        config.forbidden_techniques.clear();
+        config.required_techniques = vec!["Nothing".to_string()];
+        config.max_score = 5000;
+        config.max_attempts = 3;
+        match generate_with_config(&config) {
+            Err(SudokuError::GenerateFailed(GenerateFailReason::MaxAttempts {
+                attempts,
+                missing_technique,
+                ..
+            })) => {
+                assert_eq!(attempts, 3);
+                assert_eq!(missing_technique, 3);
+            }
+            _ => panic!("generate should fail"),
+        }
+    }
 }
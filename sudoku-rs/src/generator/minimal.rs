@@ -0,0 +1,130 @@
+use rand::{Rng, seq::SliceRandom};
+
+use crate::{
+    error::{Result, SudokuError},
+    grid::Grid,
+    solution::SolutionState,
+    solver::bitboard::BitboardSolver,
+    util::indexset::IndexSet,
+};
+
+/**
+the clues of a puzzle with unique solution, a clue is redundant when the solution is still unique
+after it is removed alone. a puzzle without redundant clue is minimal
+**/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClueAnalysis {
+    pub redundant_clues: IndexSet,
+    pub is_minimal: bool,
+}
+
+pub fn analyze_clues(grid: &Grid) -> Result<ClueAnalysis> {
+    let solver = BitboardSolver::new();
+    check_unique(&solver, grid)?;
+    let mut redundant_clues = IndexSet::new_empty();
+    for cell in clues(grid) {
+        let puzzle = remove_clue(grid, cell);
+        if solver.get_solution_state(&puzzle) == SolutionState::Unique {
+            redundant_clues.add(cell);
+        }
+    }
+    Ok(ClueAnalysis {
+        is_minimal: redundant_clues.is_empty(),
+        redundant_clues,
+    })
+}
+
+pub fn is_minimal(grid: &Grid) -> Result<bool> {
+    Ok(analyze_clues(grid)?.is_minimal)
+}
+
+// remove the clues in cell order until the puzzle is minimal
+pub fn minimize(grid: &Grid) -> Result<Grid> {
+    let cells = clues(grid);
+    minimize_in_order(grid, &cells)
+}
+
+// remove the clues in random order, different orders give different minimal puzzles
+pub fn minimize_with_rng<R: Rng>(grid: &Grid, rng: &mut R) -> Result<Grid> {
+    let mut cells = clues(grid);
+    cells.shuffle(rng);
+    minimize_in_order(grid, &cells)
+}
+
+// every clue is tried once, a clue which can't be removed now can't be removed after more clues
+// are removed
+fn minimize_in_order(grid: &Grid, cells: &[u8]) -> Result<Grid> {
+    let solver = BitboardSolver::new();
+    check_unique(&solver, grid)?;
+    let mut puzzle = grid.clone();
+    for cell in cells {
+        let removed = remove_clue(&puzzle, *cell);
+        if solver.get_solution_state(&removed) == SolutionState::Unique {
+            puzzle = removed;
+        }
+    }
+    Ok(puzzle)
+}
+
+fn check_unique(solver: &BitboardSolver, grid: &Grid) -> Result<()> {
+    match solver.get_solution_state(grid) {
+        SolutionState::Unique => Ok(()),
+        state => Err(SudokuError::InvalidInput(format!(
+            "puzzle need unique solution, got {:?}",
+            state
+        ))),
+    }
+}
+
+fn clues(grid: &Grid) -> Vec<u8> {
+    (0..81).filter(|cell| grid.get_value(*cell) != 0).collect()
+}
+
+// the candidates are calculated again from the other clues
+fn remove_clue(grid: &Grid, cell: u8) -> Grid {
+    let mut puzzle = Grid::default();
+    for other in clues(grid) {
+        if other != cell {
+            puzzle.set_value(other, grid.get_value(other), grid.cell_is_given(other));
+        }
+    }
+    puzzle
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::{
+        generator::minimal::{analyze_clues, is_minimal, minimize, minimize_with_rng},
+        grid::Grid,
+        solver::brute_force::BruteForceSolver,
+    };
+
+    #[test]
+    fn test_minimize() {
+        let s = "...16.87..1.875..38.73..651.5.62173...17..5.473.5..1...7........8.256917.62..7...";
+        let grid = Grid::new_from_singline_digit(s).unwrap();
+        let analysis = analyze_clues(&grid).unwrap();
+        assert!(!analysis.is_minimal);
+        assert!(!analysis.redundant_clues.is_empty());
+
+        let solution = BruteForceSolver::new().solve(&grid);
+        for puzzle in [
+            minimize(&grid).unwrap(),
+            minimize_with_rng(&grid, &mut StdRng::seed_from_u64(1)).unwrap(),
+        ] {
+            assert!(is_minimal(&puzzle).unwrap());
+            assert!(puzzle.clude_count() < grid.clude_count());
+            assert_eq!(
+                BruteForceSolver::new().solve(&puzzle).values(),
+                solution.values()
+            );
+            for cell in 0..81 {
+                let value = puzzle.get_value(cell);
+                assert!(value == 0 || value == grid.get_value(cell));
+            }
+        }
+        assert!(analyze_clues(&Grid::default()).is_err());
+    }
+}
@@ -1,4 +1,8 @@
+use crate::grid_constant::cell_index;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Symmetry {
+    None,
     Verital,
     Horizontal,
     Diagonal,
@@ -10,3 +14,56 @@ pub enum Symmetry {
     Full,
     Full32,
 }
+
+impl Symmetry {
+    // the cells must be all clues or all empty, the cell itself is the first one
+    pub fn orbit(&self, cell: u8) -> Vec<u8> {
+        let r = cell / 9;
+        let c = cell % 9;
+        // (row, col) of the images of the cell
+        let images: Vec<(u8, u8)> = match self {
+            Symmetry::None => vec![],
+            Symmetry::Verital => vec![(r, 8 - c)],
+            Symmetry::Horizontal => vec![(8 - r, c)],
+            Symmetry::Diagonal => vec![(c, r)],
+            Symmetry::AntiDiagonal => vec![(8 - c, 8 - r)],
+            Symmetry::BiDiagonal => vec![(c, r), (8 - c, 8 - r), (8 - r, 8 - c)],
+            Symmetry::Orthogonal => vec![(r, 8 - c), (8 - r, c), (8 - r, 8 - c)],
+            Symmetry::Rotational180 => vec![(8 - r, 8 - c)],
+            Symmetry::Rotational90 => vec![(c, 8 - r), (8 - r, 8 - c), (8 - c, r)],
+            // every rotation and mirror of the square, Full32 has the same orbits
+            Symmetry::Full | Symmetry::Full32 => vec![
+                (c, 8 - r),
+                (8 - r, 8 - c),
+                (8 - c, r),
+                (r, 8 - c),
+                (8 - r, c),
+                (c, r),
+                (8 - c, 8 - r),
+            ],
+        };
+        let mut orbit = vec![cell];
+        for (row, col) in images {
+            let image = cell_index(row, col + 9);
+            if !orbit.contains(&image) {
+                orbit.push(image);
+            }
+        }
+        orbit
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Symmetry;
+
+    #[test]
+    fn test_orbit() {
+        assert_eq!(Symmetry::None.orbit(10), vec![10]);
+        assert_eq!(Symmetry::Rotational180.orbit(0), vec![0, 80]);
+        assert_eq!(Symmetry::Rotational180.orbit(40), vec![40]);
+        assert_eq!(Symmetry::Rotational90.orbit(0), vec![0, 8, 80, 72]);
+        assert_eq!(Symmetry::Diagonal.orbit(1), vec![1, 9]);
+        assert_eq!(Symmetry::Full.orbit(1).len(), 8);
+    }
+}
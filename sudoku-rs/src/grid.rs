@@ -55,9 +55,17 @@ impl Grid {
         is_given: Vec<bool>,
     ) -> Result<Self> {
         let mut grid = Grid::default();
-        if digits.len() != 81 || pms.len() != 81 {
+        if digits.len() != 81 || pms.len() != 81 || is_given.len() != 81 {
             return Err(SudokuError::InvalidInput(format!(
-                "input digt need 81 number and 81 pms"
+                "input digt need 81 number, 81 pms and 81 givens"
+            )));
+        }
+        if let Some(cell) = (0..81)
+            .find(|cell| digits[*cell] > 9 || pms[*cell].iter().any(|v| !(1..=9).contains(v)))
+        {
+            return Err(SudokuError::InvalidInput(format!(
+                "cell {} has invalid digit",
+                cell
             )));
         }
         for (i, d) in digits.iter().enumerate() {
@@ -74,9 +82,9 @@ impl Grid {
                 grid.pential_values[cell] = DigitSet::new_from_values(pm);
             }
         }
-        for h in 0..26 {
+        for h in 0..27 {
             let cells = get_house_cell_set(h);
-            for v in 1..9 {
+            for v in 1..10 {
                 let mut n = 0;
                 for c in cells.iter() {
                     if grid.values[c as usize] == v {
@@ -94,31 +102,49 @@ impl Grid {
         Ok(grid)
     }
     pub fn new_from_singline_digit(digits: &str) -> Result<Self> {
-        if digits.len() != 81 {
-            return Err(SudokuError::InvalidInput(format!(
-                "input digit need 81 character, got {}",
-                digits.len()
-            )));
-        }
-
+        // the whitespace around the digits is allowed
+        let offset = digits.chars().take_while(|c| c.is_whitespace()).count();
+        let chars: Vec<char> = digits.trim().chars().collect();
         let mut grid = Grid::default();
-        for (index, c) in digits.as_bytes().iter().enumerate() {
+        for (index, c) in chars.iter().enumerate() {
+            let column = offset + index + 1;
+            if index >= 81 {
+                return Err(SudokuError::invalid_at(
+                    1,
+                    column,
+                    format!("expect 81 cells, got {}", chars.len()),
+                ));
+            }
             match c {
-                b'.' | b'0' => {
+                '.' | '0' => {
                     continue;
                 }
-                b'1'..=b'9' => {
-                    let value = c.to_owned() - 48;
-                    grid.set_value(index as u8, value, true);
+                '1'..='9' => {
+                    let value = *c as u8 - b'0';
+                    if !grid.set_value(index as u8, value, true) {
+                        return Err(SudokuError::invalid_at(
+                            1,
+                            column,
+                            format!("value {} conflicts with the other givens", value),
+                        ));
+                    }
                 }
                 _ => {
-                    return Err(SudokuError::InvalidInput(format!(
-                        "got invalid character:{:?}",
-                        c
-                    )));
+                    return Err(SudokuError::invalid_at(
+                        1,
+                        column,
+                        format!("invalid character {:?}", c),
+                    ));
                 }
             }
         }
+        if chars.len() != 81 {
+            return Err(SudokuError::invalid_at(
+                1,
+                offset + chars.len() + 1,
+                format!("expect 81 cells, got {}", chars.len()),
+            ));
+        }
 
         Ok(grid)
     }
@@ -299,39 +325,84 @@ impl Grid {
     pub fn new_from_hodoku_line(input: &str) -> Result<Grid> {
         let mut grid = Grid::default();
         let lines: Vec<&str> = input.split(":").collect();
-        let chars = lines[3].as_bytes();
+        if lines.len() < 4 {
+            return Err(SudokuError::invalid_at(
+                1,
+                input.chars().count() + 1,
+                "expect the puzzle in the fourth field",
+            ));
+        }
+        // the column where the puzzle starts
+        let offset = lines[..3]
+            .iter()
+            .map(|l| l.chars().count() + 1)
+            .sum::<usize>()
+            + 1;
+        let chars: Vec<char> = lines[3].chars().collect();
         let mut i = 0;
         let mut index = 0;
         while i < chars.len() {
             let char = chars[i];
+            if index >= 81 && char != ' ' {
+                return Err(SudokuError::invalid_at(1, offset + i, "more than 81 cells"));
+            }
             match char {
-                b'+' => {
-                    let d = chars[i + 1] - 48;
-                    grid.set_value(index, d, false);
+                '+' => {
+                    let d = match chars.get(i + 1) {
+                        Some(c @ '1'..='9') => *c as u8 - b'0',
+                        _ => {
+                            return Err(SudokuError::invalid_at(
+                                1,
+                                offset + i + 1,
+                                "'+' must be followed by a digit",
+                            ));
+                        }
+                    };
+                    if !grid.set_value(index, d, false) {
+                        return Err(SudokuError::invalid_at(
+                            1,
+                            offset + i + 1,
+                            format!("value {} conflicts with the other values", d),
+                        ));
+                    }
                     i += 2;
                     index += 1;
                 }
-                b'1'..=b'9' => {
-                    let d = char - 48;
-                    grid.set_value(index, d, true);
+                '1'..='9' => {
+                    let d = char as u8 - b'0';
+                    if !grid.set_value(index, d, true) {
+                        return Err(SudokuError::invalid_at(
+                            1,
+                            offset + i,
+                            format!("value {} conflicts with the other values", d),
+                        ));
+                    }
                     index += 1;
                     i += 1;
                 }
-                b'.' => {
+                '.' | '0' => {
                     i += 1;
                     index += 1
                 }
-                b' ' => {
+                ' ' => {
                     break;
                 }
                 _ => {
-                    return Err(SudokuError::InvalidInput(format!(
-                        "invalid input char in input:{:?}",
-                        char
-                    )));
+                    return Err(SudokuError::invalid_at(
+                        1,
+                        offset + i,
+                        format!("invalid character {:?}", char),
+                    ));
                 }
             }
         }
+        if index != 81 {
+            return Err(SudokuError::invalid_at(
+                1,
+                offset + i,
+                format!("expect 81 cells, got {}", index),
+            ));
+        }
         Ok(grid)
     }
 
@@ -341,28 +412,92 @@ impl Grid {
         grid.house_pential_count = [[0; 10]; 27];
         let lines: Vec<&str> = s.split("\n").collect();
         let mut index = 0;
-        for line in lines {
+        let mut empty_cells = Vec::new();
+        for (line_index, line) in lines.iter().enumerate() {
             let items: Vec<&str> = line.split(" ").collect();
             if items.len() < 13 {
                 continue;
             }
+            let mut column = 1;
+            let mut row_cells = 0;
             for item in items {
+                let item_column = column;
+                column += item.chars().count() + 1;
                 if item == "|" || item.is_empty() {
                     continue;
                 }
+                if index >= 81 {
+                    return Err(SudokuError::invalid_at(
+                        line_index + 1,
+                        item_column,
+                        "more than 81 cells",
+                    ));
+                }
+                if let Some((i, c)) = item
+                    .chars()
+                    .enumerate()
+                    .find(|(_, c)| !matches!(c, '1'..='9' | '.' | '0' | '+'))
+                {
+                    return Err(SudokuError::invalid_at(
+                        line_index + 1,
+                        item_column + i,
+                        format!("invalid character {:?}", c),
+                    ));
+                }
 
-                if item.len() == 1 {
+                // a placed value is prefixed by '+', a single candidate by '.'
+                let placed = item.strip_prefix('+');
+                if item == "." || item == "0" {
+                    // the candidates are calculated from the buddies later
+                    empty_cells.push(index);
+                } else if item.len() == 1 || placed.is_some() {
+                    let value = placed.unwrap_or(item);
+                    if value.len() != 1 || !(b'1'..=b'9').contains(&value.as_bytes()[0]) {
+                        return Err(SudokuError::invalid_at(
+                            line_index + 1,
+                            item_column,
+                            format!("invalid value {:?}", item),
+                        ));
+                    }
                     grid.unsolved_count -= 1;
-                    grid.values[index] = u8::from_str_radix(item, 10).unwrap();
+                    grid.values[index] = value.as_bytes()[0] - b'0';
                 } else {
-                    for c in item.bytes() {
-                        let cv = c - 48;
-                        grid.pential_values[index].add(cv);
+                    for c in item.strip_prefix('.').unwrap_or(item).bytes() {
+                        if !(b'1'..=b'9').contains(&c) {
+                            return Err(SudokuError::invalid_at(
+                                line_index + 1,
+                                item_column,
+                                format!("invalid candidates {:?}", item),
+                            ));
+                        }
+                        grid.pential_values[index].add(c - b'0');
                     }
                 }
                 index += 1;
+                row_cells += 1;
+            }
+            if row_cells != 9 {
+                return Err(SudokuError::invalid_at(
+                    line_index + 1,
+                    column - 1,
+                    format!("expect 9 cells in a row, got {}", row_cells),
+                ));
             }
         }
+        if index != 81 {
+            return Err(SudokuError::invalid_at(
+                lines.len(),
+                1,
+                format!("expect 81 cells, got {}", index),
+            ));
+        }
+        for cell in empty_cells {
+            let mut candidates = DigitSet::new_full();
+            for buddy in get_cell_buddies(cell as u8).iter() {
+                candidates.remove(grid.values[buddy as usize]);
+            }
+            grid.pential_values[cell] = candidates;
+        }
         for h in 0..27 {
             for cell in get_house_cell_set(h).iter() {
                 for cand in grid.pential_values[cell as usize].values() {
@@ -499,7 +634,7 @@ impl Grid {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Difficulty {
     Easy,
     Medium,
@@ -508,22 +643,26 @@ pub enum Difficulty {
     Extreme,
 }
 impl Difficulty {
+    // the score is the sum of the step difficulties of the rating solver. the bands follow the
+    // scores of generated puzzles: with 49-55 clues singles score 100-130, with 38-49 clues
+    // 130-170, 29-38 clues have a median of about 190 and only fewer clues reach 400 and more.
+    // the generator rejects the puzzles it can't solve, so Extreme has no upper bound
     pub fn min_score(&self) -> u32 {
         match self {
-            Difficulty::Easy => 400,
-            Difficulty::Medium => 800,
-            Difficulty::Hard => 1000,
-            Difficulty::UnFair => 1600,
-            Difficulty::Extreme => 2000,
+            Difficulty::Easy => 0,
+            Difficulty::Medium => 130,
+            Difficulty::Hard => 200,
+            Difficulty::UnFair => 400,
+            Difficulty::Extreme => 800,
         }
     }
     pub fn max_score(&self) -> u32 {
         match self {
-            Difficulty::Easy => 800,
-            Difficulty::Medium => 1000,
-            Difficulty::Hard => 1600,
-            Difficulty::UnFair => 2600,
-            Difficulty::Extreme => 5000,
+            Difficulty::Easy => 130,
+            Difficulty::Medium => 200,
+            Difficulty::Hard => 400,
+            Difficulty::UnFair => 800,
+            Difficulty::Extreme => u32::MAX,
         }
     }
     pub fn min_clue(&self) -> u8 {
@@ -180,6 +180,13 @@ pub fn get_cell_buddies(cell: u8) -> IndexSet {
     BUDDIES[cell as usize]
 }
 
+// cells which can see every cell of the given set
+pub fn get_common_buddies(cells: &IndexSet) -> IndexSet {
+    cells
+        .iter()
+        .fold(IndexSet::new_full(), |u, cell| u.intersect(&BUDDIES[cell as usize]))
+}
+
 pub fn get_house_cell_set(house: u8) -> IndexSet {
     HOUSE_INDEX_SET[house as usize]
 }
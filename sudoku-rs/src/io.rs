@@ -0,0 +1,522 @@
+use crate::{
+    error::{Result, SudokuError},
+    grid::Grid,
+    render::{RenderStyle, Renderer},
+    util::format_step::format_cell,
+};
+
+/**
+reading and writing the common exchange formats of sudoku:
+- Line: 81 characters in one line, '.' or '0' is an empty cell
+- SimpleSudoku: the .ss files of Simple Sudoku, 9 rows with '|' between the blocks and a line of
+  '-' between the bands
+- SadMan: the .sdk files of SadMan Sudoku, 9 rows of 9 characters, lines starting with '#' or
+  '[' are comments or headers
+- HoDoKu: a line of the HoDoKu library, ":technique:candidate:puzzle:deleted candidates:..",
+  placed values are prefixed by '+', deleted candidates are digit row column like "537"
+- Candidates: a pencilmark grid like the one printed by HoDoKu, a cell with one digit is a given,
+  placed values are prefixed by '+' and a single candidate of an empty cell by '.'
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Line,
+    SimpleSudoku,
+    SadMan,
+    HoDoKu,
+    Candidates,
+}
+
+pub fn detect_format(text: &str) -> Option<Format> {
+    let text = text.trim();
+    let lines: Vec<&str> = text
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect();
+    if lines.len() == 1 {
+        if text.starts_with(':') {
+            return Some(Format::HoDoKu);
+        }
+        if text.chars().count() == 81 {
+            return Some(Format::Line);
+        }
+        return None;
+    }
+    if candidate_rows(text).len() == 9 {
+        return Some(Format::Candidates);
+    }
+    let rows: Vec<&&str> = lines
+        .iter()
+        .filter(|line| !line.starts_with('#') && !line.starts_with('['))
+        .collect();
+    if rows
+        .iter()
+        .any(|line| line.contains('|') || line.starts_with('-'))
+    {
+        return Some(Format::SimpleSudoku);
+    }
+    if rows.len() == 9 && rows.iter().all(|line| line.chars().count() == 9) {
+        return Some(Format::SadMan);
+    }
+    None
+}
+
+// the format is detected from the text
+pub fn read(text: &str) -> Result<Grid> {
+    match detect_format(text) {
+        Some(format) => read_as(text, format),
+        None => Err(SudokuError::InvalidInput(
+            "unknown sudoku format".to_string(),
+        )),
+    }
+}
+
+pub fn read_as(text: &str, format: Format) -> Result<Grid> {
+    match format {
+        Format::Line => Grid::new_from_singline_digit(text),
+        Format::SimpleSudoku | Format::SadMan => read_rows(text),
+        Format::HoDoKu => read_hodoku(text.trim()),
+        Format::Candidates => read_candidates(text),
+    }
+}
+
+// the values of the cells, the conflicts between the values are not checked
+pub fn read_values(text: &str) -> Result<Vec<u8>> {
+    match detect_format(text) {
+        Some(Format::Line | Format::SimpleSudoku | Format::SadMan) => {
+            parse_rows(text).map(|(values, _)| values)
+        }
+        Some(Format::HoDoKu) => parse_hodoku(text.trim()).map(|(values, _, _)| values),
+        Some(Format::Candidates) => parse_candidates(text).map(|(values, _, _)| values),
+        None => Err(SudokuError::InvalidInput(
+            "unknown sudoku format".to_string(),
+        )),
+    }
+}
+
+pub fn write(grid: &Grid, format: Format) -> String {
+    match format {
+        Format::Line => grid.values().iter().map(|v| value_char(*v)).collect(),
+        Format::SimpleSudoku => write_simple_sudoku(grid),
+        Format::SadMan => {
+            let mut res = String::new();
+            for row in grid.values().chunks(9) {
+                res.extend(row.iter().map(|v| value_char(*v)));
+                res.push('\n');
+            }
+            res
+        }
+        Format::HoDoKu => write_hodoku(grid),
+        Format::Candidates => Renderer::new(RenderStyle::Pencilmark)
+            .with_placed(true)
+            .render(grid),
+    }
+}
+
+// the line and column of every cell in the text
+type Positions = Vec<(usize, usize)>;
+// the values, givens and candidates of the cells
+type CandidateCells = (Vec<u8>, Vec<bool>, Vec<Vec<u8>>);
+
+fn value_char(value: u8) -> char {
+    if value == 0 {
+        '.'
+    } else {
+        (b'0' + value) as char
+    }
+}
+
+fn cell_value(c: char) -> Option<u8> {
+    match c {
+        '.' | '0' | 'x' | 'X' => Some(0),
+        '1'..='9' => Some(c as u8 - b'0'),
+        _ => None,
+    }
+}
+
+// the candidates of the grid are calculated from the values, positions are the line and column
+// of every cell in the text
+fn build_grid(values: &[u8], given: &[bool], positions: &[(usize, usize)]) -> Result<Grid> {
+    let mut grid = Grid::default();
+    for cell in 0..81_u8 {
+        let value = values[cell as usize];
+        if value != 0 && !grid.set_value(cell, value, given[cell as usize]) {
+            let (line, column) = positions[cell as usize];
+            return Err(SudokuError::invalid_at(
+                line,
+                column,
+                format!("value {} conflicts in {}", value, format_cell(cell)),
+            ));
+        }
+    }
+    Ok(grid)
+}
+
+// the .ss and .sdk files, separators and headers are skipped
+fn read_rows(text: &str) -> Result<Grid> {
+    let (values, positions) = parse_rows(text)?;
+    let given: Vec<bool> = values.iter().map(|v| *v != 0).collect();
+    build_grid(&values, &given, &positions)
+}
+
+fn parse_rows(text: &str) -> Result<(Vec<u8>, Positions)> {
+    let mut values = Vec::new();
+    let mut positions = Vec::new();
+    for (line_index, line) in text.lines().enumerate() {
+        if line.trim_start().starts_with('#') || line.trim_start().starts_with('[') {
+            continue;
+        }
+        for (i, c) in line.chars().enumerate() {
+            if c == '|' || c == '-' || c == '+' || c.is_whitespace() {
+                continue;
+            }
+            let value = cell_value(c).ok_or_else(|| {
+                SudokuError::invalid_at(line_index + 1, i + 1, format!("invalid character {:?}", c))
+            })?;
+            if values.len() == 81 {
+                return Err(SudokuError::invalid_at(
+                    line_index + 1,
+                    i + 1,
+                    "more than 81 cells",
+                ));
+            }
+            values.push(value);
+            positions.push((line_index + 1, i + 1));
+        }
+    }
+    if values.len() != 81 {
+        return Err(SudokuError::invalid_at(
+            text.lines().count().max(1),
+            1,
+            format!("expect 81 cells, got {}", values.len()),
+        ));
+    }
+    Ok((values, positions))
+}
+
+fn write_simple_sudoku(grid: &Grid) -> String {
+    let mut res = String::new();
+    for (r, row) in grid.values().chunks(9).enumerate() {
+        if r == 3 || r == 6 {
+            res.push_str("-----------\n");
+        }
+        for (c, v) in row.iter().enumerate() {
+            if c == 3 || c == 6 {
+                res.push('|');
+            }
+            res.push(value_char(*v));
+        }
+        res.push('\n');
+    }
+    res
+}
+
+fn read_hodoku(text: &str) -> Result<Grid> {
+    let (values, given, positions) = parse_hodoku(text)?;
+    let grid = build_grid(&values, &given, &positions)?;
+    let mut pms: Vec<Vec<u8>> = (0..81)
+        .map(|cell| grid.get_cell_candidate(cell).values())
+        .collect();
+    let fields: Vec<&str> = text.split(':').collect();
+    let (_, deleted_column) = hodoku_columns(&fields);
+    let deleted = fields.get(4).unwrap_or(&"");
+    let mut column = deleted_column;
+    for item in deleted.split(' ') {
+        let digits: Vec<u8> = item.bytes().map(|b| b.wrapping_sub(b'0')).collect();
+        if !item.is_empty() {
+            if digits.len() != 3 || digits.iter().any(|d| !(1..=9).contains(d)) {
+                return Err(SudokuError::invalid_at(
+                    1,
+                    column,
+                    format!("invalid deleted candidate {:?}", item),
+                ));
+            }
+            let cell = (digits[1] - 1) * 9 + digits[2] - 1;
+            pms[cell as usize].retain(|v| *v != digits[0]);
+        }
+        column += item.chars().count() + 1;
+    }
+    Grid::new_from_digit_and_pms(&values, pms, given)
+}
+
+// the columns where the puzzle and the deleted candidates start
+fn hodoku_columns(fields: &[&str]) -> (usize, usize) {
+    let puzzle_column = fields[..3]
+        .iter()
+        .map(|f| f.chars().count() + 1)
+        .sum::<usize>()
+        + 1;
+    (puzzle_column, puzzle_column + fields[3].chars().count() + 1)
+}
+
+// the values, givens and positions of the puzzle field
+fn parse_hodoku(text: &str) -> Result<(Vec<u8>, Vec<bool>, Positions)> {
+    let fields: Vec<&str> = text.split(':').collect();
+    if fields.len() < 4 {
+        return Err(SudokuError::invalid_at(
+            1,
+            text.chars().count() + 1,
+            "expect technique, candidate and puzzle fields",
+        ));
+    }
+    let (puzzle_column, deleted_column) = hodoku_columns(&fields);
+    let mut values = Vec::new();
+    let mut given = Vec::new();
+    let mut positions = Vec::new();
+    let mut chars = fields[3].chars().enumerate();
+    while let Some((i, c)) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let (value, is_given) = if c == '+' {
+            match chars.next().and_then(|(_, c)| cell_value(c)) {
+                Some(value) if value != 0 => (value, false),
+                _ => {
+                    return Err(SudokuError::invalid_at(
+                        1,
+                        puzzle_column + i + 1,
+                        "'+' must be followed by a digit",
+                    ));
+                }
+            }
+        } else {
+            let value = cell_value(c).ok_or_else(|| {
+                SudokuError::invalid_at(1, puzzle_column + i, format!("invalid character {:?}", c))
+            })?;
+            (value, value != 0)
+        };
+        if values.len() == 81 {
+            return Err(SudokuError::invalid_at(
+                1,
+                puzzle_column + i,
+                "more than 81 cells",
+            ));
+        }
+        values.push(value);
+        given.push(is_given);
+        positions.push((1, puzzle_column + i));
+    }
+    if values.len() != 81 {
+        return Err(SudokuError::invalid_at(
+            1,
+            deleted_column - 1,
+            format!("expect 81 cells, got {}", values.len()),
+        ));
+    }
+    Ok((values, given, positions))
+}
+
+fn write_hodoku(grid: &Grid) -> String {
+    let mut puzzle = String::new();
+    let mut natural_values = [0; 81];
+    for cell in 0..81_u8 {
+        let value = grid.get_value(cell);
+        if value != 0 && !grid.cell_is_given(cell) {
+            puzzle.push('+');
+        }
+        puzzle.push(value_char(value));
+        natural_values[cell as usize] = value;
+    }
+    // the candidates which are not removed by the values
+    let mut deleted = Vec::new();
+    if let Ok(natural) = build_grid(&natural_values, grid.is_given(), &[(1, 1); 81]) {
+        for cell in 0..81_u8 {
+            if grid.get_value(cell) != 0 {
+                continue;
+            }
+            let removed = natural
+                .get_cell_candidate(cell)
+                .difference(&grid.get_cell_candidate(cell));
+            for v in removed.iter() {
+                deleted.push(format!("{}{}{}", v, cell / 9 + 1, cell % 9 + 1));
+            }
+        }
+    }
+    format!(":0000:x:{}:{}::", puzzle, deleted.join(" "))
+}
+
+// the rows with 9 cells of digits, a single candidate is prefixed by '.' and a placed value by '+'
+fn candidate_rows(text: &str) -> Vec<Vec<&str>> {
+    text.lines()
+        .map(|line| {
+            line.split(|c: char| c == '|' || c.is_whitespace())
+                .filter(|item| !item.is_empty())
+                .collect::<Vec<&str>>()
+        })
+        .filter(|items| {
+            items.len() == 9
+                && items.iter().all(|item| {
+                    let digits = item.strip_prefix(['.', '+']).unwrap_or(item);
+                    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit() && c != '0')
+                })
+        })
+        .collect()
+}
+
+fn read_candidates(text: &str) -> Result<Grid> {
+    let (values, given, pms) = parse_candidates(text)?;
+    Grid::new_from_digit_and_pms(&values, pms, given)
+}
+
+// the lines without digits are the frame of the grid
+fn parse_candidates(text: &str) -> Result<CandidateCells> {
+    let mut values = Vec::new();
+    let mut given = Vec::new();
+    let mut pms = Vec::new();
+    let mut rows = 0;
+    for (line_index, line) in text.lines().enumerate() {
+        if !line.chars().any(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        let mut cells = 0;
+        let mut column = 1;
+        for item in line.split(|c: char| c == '|' || c.is_whitespace()) {
+            let item_column = column;
+            column += item.chars().count() + 1;
+            if item.is_empty() {
+                continue;
+            }
+            // a single candidate is prefixed by '.', a placed value by '+'
+            let prefix = item.chars().next().filter(|c| *c == '.' || *c == '+');
+            let digits = &item[prefix.map_or(0, |_| 1)..];
+            if let Some((i, c)) = digits
+                .chars()
+                .enumerate()
+                .find(|(_, c)| !matches!(c, '1'..='9'))
+            {
+                return Err(SudokuError::invalid_at(
+                    line_index + 1,
+                    item_column + i + prefix.is_some() as usize,
+                    format!("invalid character {:?}", c),
+                ));
+            }
+            if prefix == Some('+') && digits.len() != 1 {
+                return Err(SudokuError::invalid_at(
+                    line_index + 1,
+                    item_column,
+                    "'+' must be followed by a digit",
+                ));
+            }
+            if cells == 9 || rows == 9 {
+                return Err(SudokuError::invalid_at(
+                    line_index + 1,
+                    item_column,
+                    "more than 9 cells in a row or 9 rows",
+                ));
+            }
+            let digits: Vec<u8> = digits.bytes().map(|b| b - b'0').collect();
+            if digits.len() == 1 && prefix != Some('.') {
+                values.push(digits[0]);
+                given.push(prefix.is_none());
+                pms.push(Vec::new());
+            } else {
+                values.push(0);
+                given.push(false);
+                pms.push(digits);
+            }
+            cells += 1;
+        }
+        if cells != 9 {
+            return Err(SudokuError::invalid_at(
+                line_index + 1,
+                column - 1,
+                format!("expect 9 cells in a row, got {}", cells),
+            ));
+        }
+        rows += 1;
+    }
+    if rows != 9 {
+        return Err(SudokuError::invalid_at(
+            text.lines().count().max(1),
+            1,
+            format!("expect 9 rows, got {}", rows),
+        ));
+    }
+    Ok((values, given, pms))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        candidate::Candidate,
+        grid::Grid,
+        io::{Format, detect_format, read, read_as, write},
+    };
+
+    fn assert_same_grid(a: &Grid, b: &Grid) {
+        assert_eq!(a.values(), b.values());
+        for cell in 0..81 {
+            assert_eq!(a.get_cell_candidate(cell), b.get_cell_candidate(cell));
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let s = "...16.87..1.875..38.73..651.5.62173...17..5.473.5..1...7........8.256917.62..7...";
+        let grid = Grid::new_from_singline_digit(s).unwrap();
+        for format in [
+            Format::Line,
+            Format::SimpleSudoku,
+            Format::SadMan,
+            Format::HoDoKu,
+            Format::Candidates,
+        ] {
+            let text = write(&grid, format);
+            assert_eq!(detect_format(&text), Some(format));
+            let again = read(&text).unwrap();
+            assert_same_grid(&again, &grid);
+            assert_eq!(again.is_given(), grid.is_given());
+            assert_eq!(write(&again, format), text);
+        }
+        assert_eq!(write(&grid, Format::Line), s);
+
+        // a placed value, a deleted candidate and r1c2 with the single candidate 9
+        let mut grid = grid.clone();
+        grid.set_value(0, 2, false);
+        grid.remvoe_candidate(&Candidate::new(1, 4));
+        for format in [Format::HoDoKu, Format::Candidates] {
+            let text = write(&grid, format);
+            assert_eq!(detect_format(&text), Some(format));
+            let again = read(&text).unwrap();
+            assert_same_grid(&again, &grid);
+            assert_eq!(again.is_given(), grid.is_given());
+            assert_eq!(write(&again, format), text);
+        }
+        let text = write(&grid, Format::Candidates);
+        assert!(text.contains("| +2     .9  3459 | 1   6 "));
+        let again = read(&text).unwrap();
+        assert!(!again.cell_is_given(0));
+        assert!(again.cell_is_given(3));
+    }
+
+    #[test]
+    fn test_read_formats() {
+        let ss = "
+..3|.1.|...
+...|...|...
+...|...|...
+-----------
+...|...|...
+...|...|...
+...|...|...
+-----------
+...|...|...
+...|...|...
+...|...|..9
+";
+        let grid = read(ss).unwrap();
+        assert_eq!(grid.get_value(2), 3);
+        assert_eq!(grid.get_value(80), 9);
+        let sdk = "#Asome one\n[Puzzle]\n".to_string()
+            + &"1........\n".repeat(1)
+            + &".........\n".repeat(8);
+        assert_eq!(detect_format(&sdk), Some(Format::SadMan));
+        assert_eq!(read(&sdk).unwrap().get_value(0), 1);
+        let hodoku = ":0800:2:+8..+36.+9....+9.1.863.+63.+89..+59+24+6+7+3+1+5+83+8+6+9+5+17+2457+182+4+3+9+6+4+3+2+1+9658+769+8+5+37......+24+8+63+9::226:";
+        let grid = read(hodoku).unwrap();
+        assert_eq!(grid.get_value(0), 8);
+        assert!(!grid.cell_is_given(0));
+        assert!(read("1234").is_err());
+        assert!(read_as("11.......".repeat(9).as_str(), Format::Line).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::DeserializeOwned};
+
+use crate::{
+    candidate::Candidate,
+    error::{Result, SudokuError},
+    grid::Grid,
+    util::{digitset::DigitSet, indexset::IndexSet},
+};
+
+/**
+the JSON shape of grids, candidates, steps and solution paths. to_json wraps the value with the
+version of the shape, from_json rejects the other versions. a candidate is
+{"cell": 0, "value": 1}, cells and digits are lists of numbers, a step is {"type": .., "data": ..}
+and a grid has 81 values, givens and candidate lists. version 2 moved the locked pairs and triples
+from NakedSet to the LockedSet step
+**/
+pub const JSON_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize)]
+struct Versioned<T> {
+    version: u32,
+    data: T,
+}
+
+pub fn to_json<T: Serialize>(value: &T) -> Result<String> {
+    let versioned = Versioned {
+        version: JSON_VERSION,
+        data: value,
+    };
+    serde_json::to_string(&versioned).map_err(|e| SudokuError::InvalidInput(e.to_string()))
+}
+
+pub fn from_json<T: DeserializeOwned>(text: &str) -> Result<T> {
+    let versioned: Versioned<serde_json::Value> =
+        serde_json::from_str(text).map_err(|e| SudokuError::InvalidInput(e.to_string()))?;
+    if versioned.version != JSON_VERSION {
+        return Err(SudokuError::InvalidInput(format!(
+            "json version {} is not supported, expect {}",
+            versioned.version, JSON_VERSION
+        )));
+    }
+    serde_json::from_value(versioned.data).map_err(|e| SudokuError::InvalidInput(e.to_string()))
+}
+
+fn invalid<E: serde::de::Error>(reason: String) -> E {
+    E::custom(reason)
+}
+
+#[derive(Serialize, Deserialize)]
+struct CandidateData {
+    cell: u8,
+    value: u8,
+}
+
+impl Serialize for Candidate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        CandidateData {
+            cell: self.cell(),
+            value: self.value(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Candidate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let data = CandidateData::deserialize(deserializer)?;
+        if data.cell > 80 || !(1..=9).contains(&data.value) {
+            return Err(invalid(format!(
+                "invalid candidate {} in cell {}",
+                data.value, data.cell
+            )));
+        }
+        Ok(Candidate::new(data.cell, data.value))
+    }
+}
+
+impl Serialize for IndexSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.values().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for IndexSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let cells = Vec::<u8>::deserialize(deserializer)?;
+        if let Some(cell) = cells.iter().find(|c| **c > 80) {
+            return Err(invalid(format!("invalid cell {}", cell)));
+        }
+        Ok(IndexSet::new_from_values(cells.into_iter()))
+    }
+}
+
+impl Serialize for DigitSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.values().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DigitSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let digits = Vec::<u8>::deserialize(deserializer)?;
+        if let Some(digit) = digits.iter().find(|d| !(1..=9).contains(*d)) {
+            return Err(invalid(format!("invalid digit {}", digit)));
+        }
+        Ok(DigitSet::new_from_values(&digits))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct GridData {
+    values: Vec<u8>,
+    given: Vec<bool>,
+    candidates: Vec<Vec<u8>>,
+}
+
+impl Serialize for Grid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        GridData {
+            values: self.values().to_vec(),
+            given: self.is_given().to_vec(),
+            candidates: (0..81)
+                .map(|cell| self.get_cell_candidate(cell).values())
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Grid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let data = GridData::deserialize(deserializer)?;
+        if data.given.len() != 81 {
+            return Err(invalid("grid needs 81 givens".to_string()));
+        }
+        if data.values.iter().any(|v| *v > 9)
+            || data
+                .candidates
+                .iter()
+                .flatten()
+                .any(|v| !(1..=9).contains(v))
+        {
+            return Err(invalid("grid values must be digits".to_string()));
+        }
+        Grid::new_from_digit_and_pms(&data.values, data.candidates, data.given)
+            .map_err(|e| invalid(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        candidate::Candidate,
+        grid::Grid,
+        json::{from_json, to_json},
+        solution::SolutionPath,
+        solver::{SimpleSolver, hidden_single::HiddenSingle, locked_set::LockedSet, step::Step},
+    };
+
+    #[test]
+    fn test_json_round_trip() {
+        let cand = Candidate::new(10, 3);
+        assert_eq!(
+            to_json(&cand).unwrap(),
+            r#"{"version":2,"data":{"cell":10,"value":3}}"#
+        );
+        assert_eq!(
+            from_json::<Candidate>(&to_json(&cand).unwrap()).unwrap(),
+            cand
+        );
+        assert!(from_json::<Candidate>(r#"{"version":1,"data":{"cell":10,"value":3}}"#).is_err());
+        assert!(from_json::<Candidate>(r#"{"version":2,"data":{"cell":81,"value":3}}"#).is_err());
+
+        let step = Step::HiddenSingle(HiddenSingle::new(10, 1, 3));
+        assert_eq!(
+            to_json(&step).unwrap(),
+            r#"{"version":2,"data":{"type":"HiddenSingle","data":{"candidate":{"cell":10,"value":3},"house":1}}}"#
+        );
+
+        // a locked pair of version 1 is a naked set with the locked fields
+        let old = r#"{"version":1,"data":{"type":"NakedSet","data":{"degree":2,"remove_candidates":[],"highlight_candidates":[],"house":1,"locked_house":19,"locked":true}}}"#;
+        assert!(from_json::<Step>(old).is_err());
+        let locked = Step::LockedSet(LockedSet {
+            degree: 2,
+            block: 19,
+            line: 1,
+            highlight_candidates: vec![Candidate::new(12, 4), Candidate::new(12, 7)],
+            remove_candidates: vec![Candidate::new(10, 4)],
+        });
+        assert_eq!(
+            to_json(&locked).unwrap(),
+            r#"{"version":2,"data":{"type":"LockedSet","data":{"degree":2,"block":19,"line":1,"highlight_candidates":[{"cell":12,"value":4},{"cell":12,"value":7}],"remove_candidates":[{"cell":10,"value":4}]}}}"#
+        );
+        assert_eq!(
+            from_json::<Step>(&to_json(&locked).unwrap()).unwrap(),
+            locked
+        );
+
+        let s = "...16.87..1.875..38.73..651.5.62173...17..5.473.5..1...7........8.256917.62..7...";
+        let grid = Grid::new_from_singline_digit(s).unwrap();
+        let again: Grid = from_json(&to_json(&grid).unwrap()).unwrap();
+        assert_eq!(again.values(), grid.values());
+        assert_eq!(again.is_given(), grid.is_given());
+        for cell in 0..81 {
+            assert_eq!(
+                again.get_cell_candidate(cell),
+                grid.get_cell_candidate(cell)
+            );
+        }
+        for house in 0..27 {
+            for value in 1..=9 {
+                assert_eq!(
+                    again.get_house_pential_count(house, value),
+                    grid.get_house_pential_count(house, value)
+                );
+            }
+        }
+
+        let solution = SimpleSolver::new().solve(&mut grid.clone());
+        let text = to_json(&solution).unwrap();
+        let again: SolutionPath = from_json(&text).unwrap();
+        assert_eq!(again.steps(), solution.steps());
+        assert_eq!(again.score(), solution.score());
+    }
+}
@@ -1,9 +1,15 @@
 pub mod error;
 pub mod generator;
 pub mod grid;
+pub mod io;
+#[cfg(feature = "serde")]
+pub mod json;
+pub mod render;
+pub mod session;
 pub mod solution;
 pub mod solver;
 pub mod util;
+pub mod validation;
 
 pub mod candidate;
 mod grid_constant;
@@ -0,0 +1,183 @@
+use std::fmt;
+
+use crate::grid::Grid;
+
+/**
+the layouts a grid can be printed in, every layout can be read back by Grid::new_from_matrix_str
+- Plain: the values only, '.' is an empty cell
+- Pencilmark: the candidates of the empty cells, the columns are aligned to the widest cell. an
+  empty cell with a single candidate is prefixed by '.' so it is not read back as solved
+- Compact: like Pencilmark but the cells are not padded
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderStyle {
+    Plain,
+    Pencilmark,
+    Compact,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Renderer {
+    pub style: RenderStyle,
+    // the lines above, between and below the bands
+    pub frame: bool,
+    // the values which are not givens are prefixed by '+' like HoDoKu
+    pub placed: bool,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Renderer::new(RenderStyle::Pencilmark)
+    }
+}
+
+impl Renderer {
+    pub fn new(style: RenderStyle) -> Self {
+        Renderer {
+            style,
+            frame: true,
+            placed: false,
+        }
+    }
+
+    pub fn with_frame(mut self, frame: bool) -> Self {
+        self.frame = frame;
+        self
+    }
+
+    pub fn with_placed(mut self, placed: bool) -> Self {
+        self.placed = placed;
+        self
+    }
+
+    pub fn render(&self, grid: &Grid) -> String {
+        let items: Vec<String> = (0..81_u8)
+            .map(|cell| match grid.get_value(cell) {
+                0 if self.style == RenderStyle::Plain => ".".to_string(),
+                0 => {
+                    let candidates = grid.get_cell_candidate(cell);
+                    match candidates.count() {
+                        0 => ".".to_string(),
+                        1 => format!(".{}", candidates.values()[0]),
+                        _ => candidates.iter().map(|v| v.to_string()).collect(),
+                    }
+                }
+                v if self.placed && !grid.cell_is_given(cell) => format!("+{}", v),
+                v => v.to_string(),
+            })
+            .collect();
+        let widths: Vec<usize> = (0..9)
+            .map(|c| match self.style {
+                RenderStyle::Pencilmark => (0..9).map(|r| items[r * 9 + c].len()).max().unwrap(),
+                _ => 0,
+            })
+            .collect();
+        let separator = if self.style == RenderStyle::Pencilmark {
+            "  "
+        } else {
+            " "
+        };
+        let rows: Vec<Vec<String>> = (0..9)
+            .map(|r| {
+                (0..3)
+                    .map(|b| {
+                        let cells: Vec<String> = (b * 3..b * 3 + 3)
+                            .map(|c| format!("{:width$}", items[r * 9 + c], width = widths[c]))
+                            .collect();
+                        format!(" {} ", cells.join(separator))
+                    })
+                    .collect()
+            })
+            .collect();
+        // the width of every block between two '|'
+        let block_widths: Vec<usize> = (0..3)
+            .map(|b| rows.iter().map(|row| row[b].len()).max().unwrap())
+            .collect();
+        let border = |left: char, middle: char, right: char| {
+            let segments: Vec<String> = block_widths.iter().map(|w| "-".repeat(*w)).collect();
+            format!("{}{}{}\n", left, segments.join(&middle.to_string()), right)
+        };
+
+        let mut res = String::new();
+        if self.frame {
+            res.push_str(&border('.', '.', '.'));
+        }
+        for (r, row) in rows.iter().enumerate() {
+            if self.frame && (r == 3 || r == 6) {
+                res.push_str(&border(':', '+', ':'));
+            }
+            for block in row {
+                res.push('|');
+                res.push_str(block);
+            }
+            res.push_str("|\n");
+        }
+        if self.frame {
+            res.push_str(&border('\'', '\'', '\''));
+        }
+        res
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Renderer::default().render(self))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        candidate::Candidate,
+        grid::Grid,
+        render::{RenderStyle, Renderer},
+    };
+
+    #[test]
+    fn test_render_round_trip() {
+        let s = "...16.87..1.875..38.73..651.5.62173...17..5.473.5..1...7........8.256917.62..7...";
+        let mut grid = Grid::new_from_singline_digit(s).unwrap();
+        grid.remvoe_candidate(&Candidate::new(1, 4));
+        // r1c2 is left with the candidate 9
+        grid.remvoe_candidate(&Candidate::new(1, 2));
+
+        let plain = Renderer::new(RenderStyle::Plain).render(&grid);
+        assert!(plain.starts_with(".-------.-------.-------.\n| . . . | 1 6 . | 8 7 . |\n"));
+        let again = Grid::new_from_matrix_str(&plain).unwrap();
+        assert_eq!(again.values(), grid.values());
+
+        for renderer in [
+            Renderer::new(RenderStyle::Pencilmark),
+            Renderer::new(RenderStyle::Compact),
+            Renderer::new(RenderStyle::Pencilmark).with_frame(false),
+        ] {
+            let text = renderer.render(&grid);
+            let again = Grid::new_from_matrix_str(&text).unwrap();
+            assert_eq!(again.values(), grid.values());
+            for cell in 0..81 {
+                assert_eq!(
+                    again.get_cell_candidate(cell),
+                    grid.get_cell_candidate(cell)
+                );
+            }
+            assert_eq!(renderer.render(&again), text);
+        }
+        let text = Renderer::new(RenderStyle::Compact).render(&grid);
+        assert!(text.contains("| 23459 .9 3459 |"));
+
+        // the placed values are marked only on request
+        grid.set_value(0, 3, false);
+        let text = Renderer::new(RenderStyle::Plain)
+            .with_placed(true)
+            .render(&grid);
+        assert!(text.contains("| +3 . . | 1 6 . |"));
+        let again = Grid::new_from_matrix_str(&text).unwrap();
+        assert_eq!(again.values(), grid.values());
+        let text = Renderer::new(RenderStyle::Plain).render(&grid);
+        assert!(text.contains("| 3 . . | 1 6 . |"));
+        assert_eq!(
+            grid.to_string(),
+            Renderer::new(RenderStyle::Pencilmark).render(&grid)
+        );
+    }
+}
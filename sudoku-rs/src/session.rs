@@ -0,0 +1,282 @@
+use crate::{
+    candidate::Candidate,
+    error::{Result, SudokuError},
+    grid::Grid,
+    grid_constant::get_cell_buddies,
+    solution::SolutionState,
+    solver::{SimpleSolver, brute_force::BruteForceSolver, step::Step},
+};
+
+/**
+a game session keeps the grid of the player, every move is recorded with the grid before it,
+so undo restores the old grid and redo plays the move again.
+the solution from the BruteForceSolver is used to find the mistakes of the player
+**/
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Move {
+    // value 0 clears the cell
+    SetValue { cell: u8, value: u8 },
+    RemoveCandidate(Candidate),
+    ApplyStep(Step),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mistake {
+    // the value set by the player is not the solution of the cell
+    WrongValue(Candidate),
+    // the solution of the cell is removed from its candidates
+    RemovedSolution(Candidate),
+}
+
+impl Mistake {
+    pub fn cell(&self) -> u8 {
+        match self {
+            Mistake::WrongValue(cand) | Mistake::RemovedSolution(cand) => cand.cell(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Session {
+    grid: Grid,
+    solution: [u8; 81],
+    history: Vec<(Move, Grid)>,
+    redo_moves: Vec<Move>,
+}
+
+impl Session {
+    pub fn new(grid: Grid) -> Result<Self> {
+        let solution = BruteForceSolver::new().solve(&grid);
+        if solution.state() != &SolutionState::Unique {
+            return Err(SudokuError::InvalidInput(format!(
+                "sudoku need unique solution, got {:?}",
+                solution.state()
+            )));
+        }
+        Ok(Session {
+            grid,
+            solution: solution.values().to_owned(),
+            history: Vec::new(),
+            redo_moves: Vec::new(),
+        })
+    }
+
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    pub fn solution(&self) -> &[u8; 81] {
+        &self.solution
+    }
+
+    pub fn moves(&self) -> Vec<&Move> {
+        self.history.iter().map(|(m, _)| m).collect()
+    }
+
+    pub fn set_value(&mut self, cell: u8, value: u8) -> Result<()> {
+        self.play(Move::SetValue { cell, value })
+    }
+
+    pub fn remove_candidate(&mut self, candidate: Candidate) -> Result<()> {
+        self.play(Move::RemoveCandidate(candidate))
+    }
+
+    pub fn apply_step(&mut self, step: Step) -> Result<()> {
+        self.play(Move::ApplyStep(step))
+    }
+
+    // the next step of the solver, the session is not changed
+    pub fn hint(&self, solver: &SimpleSolver) -> Step {
+        solver.hint(&self.grid)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_moves.is_empty()
+    }
+
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some((m, grid)) => {
+                self.grid = grid;
+                self.redo_moves.push(m);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        let Some(m) = self.redo_moves.pop() else {
+            return false;
+        };
+        let before = self.grid.clone();
+        // the move was valid on the same grid when it was played
+        if Self::apply_move(&mut self.grid, &m).is_err() {
+            self.grid = before;
+            return false;
+        }
+        self.history.push((m, before));
+        true
+    }
+
+    pub fn mistakes(&self) -> Vec<Mistake> {
+        let mut mistakes = Vec::new();
+        for cell in 0..81_u8 {
+            let solution = self.solution[cell as usize];
+            let value = self.grid.get_value(cell);
+            if value != 0 && value != solution {
+                mistakes.push(Mistake::WrongValue(Candidate::new(cell, value)));
+            } else if value == 0
+                && !self.grid.cell_has_candidate(cell, solution)
+                && !self.wrong_value_in_buddies(cell, solution)
+            {
+                mistakes.push(Mistake::RemovedSolution(Candidate::new(cell, solution)));
+            }
+        }
+        mistakes
+    }
+
+    // the candidate is removed by a wrong value, not by the player
+    fn wrong_value_in_buddies(&self, cell: u8, value: u8) -> bool {
+        get_cell_buddies(cell)
+            .iter()
+            .any(|buddy| self.grid.get_value(buddy) == value)
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.grid.is_solved() && self.grid.values() == &self.solution
+    }
+
+    // a new move drops the moves can be redone
+    fn play(&mut self, m: Move) -> Result<()> {
+        let before = self.grid.clone();
+        if let Err(e) = Self::apply_move(&mut self.grid, &m) {
+            self.grid = before;
+            return Err(e);
+        }
+        self.history.push((m, before));
+        self.redo_moves.clear();
+        Ok(())
+    }
+
+    fn apply_move(grid: &mut Grid, m: &Move) -> Result<()> {
+        match m {
+            Move::SetValue { cell, value } => {
+                if *cell > 80 || *value > 9 {
+                    return Err(SudokuError::InvalidInput(format!(
+                        "invalid value {} in cell {}",
+                        value, cell
+                    )));
+                }
+                if grid.cell_is_given(*cell) {
+                    return Err(SudokuError::GridStateError(format!(
+                        "cell {} is given",
+                        cell
+                    )));
+                }
+                if !grid.set_value(*cell, *value, false) {
+                    return Err(SudokuError::GridStateError(format!(
+                        "value {} conflicts in cell {}",
+                        value, cell
+                    )));
+                }
+            }
+            Move::RemoveCandidate(cand) => {
+                if cand.cell() > 80 || !(1..=9).contains(&cand.value()) {
+                    return Err(SudokuError::InvalidInput(format!(
+                        "invalid candidate {} in cell {}",
+                        cand.value(),
+                        cand.cell()
+                    )));
+                }
+                if !grid.cell_has_candidate(cand.cell(), cand.value()) {
+                    return Err(SudokuError::GridStateError(format!(
+                        "cell {} has no candidate {}",
+                        cand.cell(),
+                        cand.value()
+                    )));
+                }
+                grid.remvoe_candidate(cand);
+            }
+            Move::ApplyStep(step) => {
+                if step == &Step::Nothing {
+                    return Err(SudokuError::GridStateError("no step to apply".to_string()));
+                }
+                step.apply(grid);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        candidate::Candidate,
+        error::SudokuError,
+        grid::Grid,
+        session::{Mistake, Session},
+        solver::SimpleSolver,
+    };
+
+    #[test]
+    fn test_session_undo_redo() {
+        let s = "...16.87..1.875..38.73..651.5.62173...17..5.473.5..1...7........8.256917.62..7...";
+        let grid = Grid::new_from_singline_digit(s).unwrap();
+        let mut session = Session::new(grid.clone()).unwrap();
+        assert!(!session.can_undo());
+        let solution = session.solution()[0];
+        let wrong = grid
+            .get_cell_candidate(0)
+            .iter()
+            .find(|v| *v != solution)
+            .unwrap();
+        session.set_value(0, wrong).unwrap();
+        assert_eq!(
+            session.mistakes(),
+            vec![Mistake::WrongValue(Candidate::new(0, wrong))]
+        );
+        assert!(session.set_value(3, 1).is_err());
+        for value in [0, 10, 18] {
+            assert!(matches!(
+                session.remove_candidate(Candidate::new(1, value)),
+                Err(SudokuError::InvalidInput(_))
+            ));
+        }
+        assert!(session.undo());
+        assert!(session.mistakes().is_empty());
+        assert_eq!(session.grid().values(), grid.values());
+        assert!(session.redo());
+        assert_eq!(session.grid().get_value(0), wrong);
+        session.undo();
+
+        session
+            .remove_candidate(Candidate::new(0, solution))
+            .unwrap();
+        assert_eq!(
+            session.mistakes(),
+            vec![Mistake::RemovedSolution(Candidate::new(0, solution))]
+        );
+        session.undo();
+        assert!(session.can_redo());
+        assert!(!session.can_undo());
+
+        let solver = SimpleSolver::new();
+        while !session.is_solved() {
+            let step = session.hint(&solver);
+            session.apply_step(step).unwrap();
+        }
+        assert!(session.mistakes().is_empty());
+        let moves = session.moves().len();
+        assert!(session.undo());
+        assert!(!session.is_solved());
+        session.set_value(0, solution).unwrap();
+        assert!(!session.can_redo());
+        assert_eq!(session.moves().len(), moves);
+    }
+}
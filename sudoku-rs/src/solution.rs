@@ -11,22 +11,39 @@ pub enum SolutionState {
 pub struct Solution {
     values: [u8; 81],
     state: SolutionState,
+    // another solution when the solution is not unique
+    other: Option<[u8; 81]>,
 }
 
 impl Solution {
     pub fn new(values: [u8; 81], state: SolutionState) -> Self {
-        Self { values, state }
+        Self {
+            values,
+            state,
+            other: None,
+        }
     }
+
+    pub fn with_other(mut self, other: [u8; 81]) -> Self {
+        self.other = Some(other);
+        self
+    }
+
     pub fn values(&self) -> &[u8; 81] {
         &self.values
     }
 
+    pub fn other_values(&self) -> Option<&[u8; 81]> {
+        self.other.as_ref()
+    }
+
     pub fn state(&self) -> &SolutionState {
         &self.state
     }
 }
 
 #[derive(Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SolutionPath {
     steps: Vec<Step>,
     score: u32,
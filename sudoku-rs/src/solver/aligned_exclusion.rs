@@ -0,0 +1,324 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use itertools::Itertools;
+
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    grid_constant::{get_cell_buddies, get_common_buddies},
+    solver::{
+        SolverStrategy,
+        als::{Als, find_all_als},
+        step::Step,
+        step_accumulator::StepAccumulator,
+    },
+    util::{
+        create_permutations,
+        digitset::DigitSet,
+        format_step::{format_candidates_cells, format_candidates_values},
+        indexset::IndexSet,
+    },
+};
+
+/**
+aligned exclusion: base cells which see each other can't have the same value, and a combination of
+their values can't remove two values from an als (a bivalue cell is the smallest one) because its
+cells would be left with too few values. a candidate of a base cell which is in no possible
+combination can be removed. size 2 is Aligned Pair Exclusion, 3 is Aligned Triple Exclusion
+**/
+#[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlignedExclusion {
+    pub size: u8,
+    pub base_candidates: Vec<Candidate>,
+    // the alses which exclude the combinations with a removed candidate
+    pub alses: Vec<Als>,
+    pub remove_candidates: Vec<Candidate>,
+}
+
+impl AlignedExclusion {
+    pub fn apply(&self, grid: &mut Grid) {
+        for cand in self.remove_candidates.iter() {
+            grid.remvoe_candidate(cand);
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self.size {
+            2 => "Aligned Pair Exclusion",
+            _ => "Aligned Triple Exclusion",
+        }
+    }
+
+    pub fn difficulty(&self) -> u32 {
+        match self.size {
+            2 => 250,
+            _ => 270,
+        }
+    }
+
+    pub fn explain(&self) -> String {
+        let mut res = format!(
+            "<h3>{}</h3><p>base cells {} see each other<br>",
+            self.name(),
+            format_candidates_cells(&self.base_candidates)
+        );
+        for (i, als) in self.alses.iter().enumerate() {
+            res.push_str(format!("ALS {}: {}<br>", i + 1, als.format()).as_str());
+        }
+        res.push_str(
+            format!(
+                "no possible combination of the base cells has {} in {}, it can be removed</p>",
+                format_candidates_values(&self.remove_candidates),
+                format_candidates_cells(&self.remove_candidates)
+            )
+            .as_str(),
+        );
+        res
+    }
+}
+
+pub struct AlignedExclusionFinder {
+    size: u8,
+}
+
+impl AlignedExclusionFinder {
+    pub fn new(size: u8) -> Self {
+        AlignedExclusionFinder { size }
+    }
+
+    // for every cell, the alses and their values which are removed when the cell has the value:
+    // the cell sees every cell of the als with the value
+    fn find_als_values(&self, grid: &Grid, alses: &[Als]) -> Vec<Vec<(usize, DigitSet)>> {
+        let mut als_values = vec![Vec::new(); 81];
+        for (i, als) in alses.iter().enumerate() {
+            let mut cell_values: BTreeMap<u8, DigitSet> = BTreeMap::new();
+            for v in als.values.iter() {
+                let buddies = get_common_buddies(&als.value_cells(v)).difference(&als.cells);
+                for cell in buddies.iter().filter(|c| grid.cell_has_candidate(*c, v)) {
+                    cell_values
+                        .entry(cell)
+                        .or_insert(DigitSet::new_empty())
+                        .add(v);
+                }
+            }
+            for (cell, values) in cell_values {
+                als_values[cell as usize].push((i, values));
+            }
+        }
+        als_values
+    }
+
+    fn is_aligned(&self, cells: &[u8]) -> bool {
+        cells
+            .iter()
+            .tuple_combinations()
+            .all(|(a, b)| get_cell_buddies(*a).contains(*b))
+    }
+
+    // true when the accumulator is full
+    fn check_base(
+        &self,
+        grid: &Grid,
+        base: &[u8],
+        alses: &[Als],
+        als_values: &[Vec<(usize, DigitSet)>],
+        acc: &mut dyn StepAccumulator,
+    ) -> bool {
+        // the values of every als which each base cell removes, only the alses which can lose
+        // two values are kept. ordered by the als index, the first als excluding a combination is
+        // the same in every run
+        let base_set = IndexSet::new_from_values(base.iter().copied());
+        let mut removed: BTreeMap<usize, Vec<DigitSet>> = BTreeMap::new();
+        for (i, cell) in base.iter().enumerate() {
+            for (als, values) in als_values[*cell as usize].iter() {
+                removed
+                    .entry(*als)
+                    .or_insert(vec![DigitSet::new_empty(); base.len()])[i] = *values;
+            }
+        }
+        removed.retain(|als, values| {
+            alses[*als].cells.intersect(&base_set).is_empty()
+                && values
+                    .iter()
+                    .fold(DigitSet::new_empty(), |u, v| u.union(v))
+                    .count()
+                    >= 2
+        });
+        if removed.is_empty() {
+            return false;
+        }
+
+        let mut possible = vec![DigitSet::new_empty(); base.len()];
+        let mut excluded: Vec<(Vec<u8>, usize)> = Vec::new();
+        for combination in base
+            .iter()
+            .map(|cell| grid.get_cell_candidate(*cell).values())
+            .multi_cartesian_product()
+        {
+            if !combination.iter().all_unique() {
+                continue;
+            }
+            let excluding = removed.iter().find(|(_, values)| {
+                let mut lost = DigitSet::new_empty();
+                for (i, v) in combination.iter().enumerate() {
+                    if values[i].contains(*v) {
+                        lost.add(*v);
+                    }
+                }
+                lost.count() >= 2
+            });
+            match excluding {
+                Some((als, _)) => excluded.push((combination, *als)),
+                None => {
+                    for (i, v) in combination.iter().enumerate() {
+                        possible[i].add(*v);
+                    }
+                }
+            }
+        }
+
+        let mut remove_candidates = Vec::new();
+        for (i, cell) in base.iter().enumerate() {
+            for v in grid.get_cell_candidate(*cell).iter() {
+                if !possible[i].contains(v) {
+                    remove_candidates.push(Candidate::new(*cell, v));
+                }
+            }
+        }
+        // without als the eliminations are a naked subset
+        let used: BTreeSet<usize> = excluded
+            .iter()
+            .filter(|(combination, _)| {
+                combination
+                    .iter()
+                    .enumerate()
+                    .any(|(i, v)| !possible[i].contains(*v))
+            })
+            .map(|(_, als)| *als)
+            .collect();
+        if remove_candidates.is_empty() || used.is_empty() {
+            return false;
+        }
+        let base_candidates = base
+            .iter()
+            .flat_map(|cell| {
+                grid.get_cell_candidate(*cell)
+                    .iter()
+                    .map(|v| Candidate::new(*cell, v))
+                    .collect::<Vec<Candidate>>()
+            })
+            .collect();
+        let step = AlignedExclusion {
+            size: self.size,
+            base_candidates,
+            alses: used.iter().map(|i| alses[*i].clone()).collect(),
+            remove_candidates,
+        };
+        acc.add_step(Step::AlignedExclusion(step))
+    }
+
+    pub fn find_aligned_exclusion(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        let alses = find_all_als(grid);
+        let als_values = self.find_als_values(grid, &alses);
+        for first in 0..81_u8 {
+            if grid.get_cell_candidate(first).count() < 2 {
+                continue;
+            }
+            let buddies: Vec<u8> = get_cell_buddies(first)
+                .iter()
+                .filter(|c| *c > first && grid.get_cell_candidate(*c).count() >= 2)
+                .collect();
+            if buddies.len() < (self.size - 1) as usize {
+                continue;
+            }
+            for others in create_permutations(buddies, self.size - 1) {
+                let mut base = vec![first];
+                base.extend(others);
+                if !self.is_aligned(&base) {
+                    continue;
+                }
+                if self.check_base(grid, &base, &alses, &als_values, acc) {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl SolverStrategy for AlignedExclusionFinder {
+    fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        self.find_aligned_exclusion(grid, acc);
+    }
+
+    fn name(&self) -> &str {
+        "AlignedExclusionFinder"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        candidate::Candidate,
+        grid::Grid,
+        solver::{
+            SolverStrategy, aligned_exclusion::AlignedExclusionFinder, bitboard::BitboardSolver,
+            step::Step, step_accumulator::AllStepAccumulator,
+        },
+    };
+
+    #[test]
+    fn test_aligned_exclusion() {
+        let s = ".-------------.-------------.----------.
+| 7   25  9   | 15   12   4 | 8   6  3 |
+| 1   35  6   | 35   9    8 | 7   2  4 |
+| 4   8   23  | 37   237  6 | 9   1  5 |
+:-------------+-------------+----------:
+| 9   23  8   | 136  13   5 | 26  4  7 |
+| 25  4   235 | 367  37   9 | 26  8  1 |
+| 6   7   1   | 8    4    2 | 3   5  9 |
+:-------------+-------------+----------:
+| 8   1   7   | 2    5    3 | 4   9  6 |
+| 3   6   4   | 9    8    1 | 5   7  2 |
+| 25  9   25  | 4    6    7 | 1   3  8 |
+'-------------'-------------'----------'";
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let solution = *BitboardSolver::new().solve(&grid).values();
+        for size in [2, 3] {
+            let mut acc = AllStepAccumulator::default();
+            AlignedExclusionFinder::new(size).find_step(&grid, &mut acc);
+            let steps = acc.get_steps();
+            assert!(!steps.is_empty());
+            for step in steps.iter() {
+                let Step::AlignedExclusion(ae) = step else {
+                    panic!("not aligned exclusion step");
+                };
+                assert_eq!(ae.size, size);
+                assert!(!ae.alses.is_empty());
+                for cand in ae.remove_candidates.iter() {
+                    assert_ne!(solution[cand.cell() as usize], cand.value());
+                }
+            }
+        }
+
+        // r2c2 excludes 5 and 3 in r1c2 and r4c2, r1c5 and r4c5 exclude 2 and 3
+        let mut acc = AllStepAccumulator::default();
+        AlignedExclusionFinder::new(2).find_step(&grid, &mut acc);
+        assert!(acc.get_steps().iter().any(|step| match step {
+            Step::AlignedExclusion(ae) =>
+                ae.remove_candidates == vec![Candidate::new(1, 2), Candidate::new(28, 3)],
+            _ => false,
+        }));
+
+        // the same alses explain the steps in every run
+        for size in [2, 3] {
+            let mut first = AllStepAccumulator::default();
+            AlignedExclusionFinder::new(size).find_step(&grid, &mut first);
+            for _ in 0..10 {
+                let mut acc = AllStepAccumulator::default();
+                AlignedExclusionFinder::new(size).find_step(&grid, &mut acc);
+                assert_eq!(acc.get_steps(), first.get_steps());
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    solver::{
+        SolverStrategy,
+        als::{Als, AlsStep, AlsType, find_all_als, find_eliminations, find_rcc_links},
+        step::Step,
+        step_accumulator::StepAccumulator,
+    },
+    util::digitset::DigitSet,
+};
+
+// ALS-XZ is a chain of 2 alses, ALS-XY-Wing is a chain of 3
+const MIN_CHAIN_LENGTH: usize = 4;
+const MAX_CHAIN_LENGTH: usize = 6;
+
+#[derive(Default)]
+pub struct AlsChainFinder {}
+
+impl AlsChainFinder {
+    pub fn find_als_chain(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        let alses = find_all_als(grid);
+        let links = find_rcc_links(&alses);
+        for start in 0..alses.len() {
+            let mut chain = vec![start];
+            let mut rccs = Vec::new();
+            self.search(grid, acc, &alses, &links, &mut chain, &mut rccs);
+            if acc.is_finish() {
+                return;
+            }
+        }
+    }
+
+    fn search(
+        &self,
+        grid: &Grid,
+        acc: &mut dyn StepAccumulator,
+        alses: &[Als],
+        links: &[Vec<(usize, DigitSet)>],
+        chain: &mut Vec<usize>,
+        rccs: &mut Vec<u8>,
+    ) {
+        let last = *chain.last().unwrap();
+        for (next, next_rccs) in links[last].iter() {
+            if chain.contains(next) || chain.iter().any(|a| alses[*a].is_overlap(&alses[*next])) {
+                continue;
+            }
+            for rcc in next_rccs.iter() {
+                // the same als can not be entered and left by the same digit
+                if rccs.last() == Some(&rcc) {
+                    continue;
+                }
+                chain.push(*next);
+                rccs.push(rcc);
+                // every chain is found from both ends, only keep the one starts with smaller index
+                if chain.len() >= MIN_CHAIN_LENGTH && chain[0] < *next {
+                    self.check_chain(grid, acc, alses, chain, rccs);
+                }
+                if !acc.is_finish() && chain.len() < MAX_CHAIN_LENGTH {
+                    self.search(grid, acc, alses, links, chain, rccs);
+                }
+                chain.pop();
+                rccs.pop();
+                if acc.is_finish() {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn check_chain(
+        &self,
+        grid: &Grid,
+        acc: &mut dyn StepAccumulator,
+        alses: &[Als],
+        chain: &[usize],
+        rccs: &[u8],
+    ) {
+        let first = &alses[chain[0]];
+        let last = &alses[*chain.last().unwrap()];
+        let mut remove_candidates: Vec<Candidate> = Vec::new();
+        for z in first.values.intersect(&last.values).iter() {
+            if Some(&z) == rccs.first() || Some(&z) == rccs.last() {
+                continue;
+            }
+            remove_candidates.extend(find_eliminations(grid, &[first, last], z));
+        }
+        if remove_candidates.is_empty() {
+            return;
+        }
+        let step = AlsStep {
+            als_type: AlsType::Chain,
+            alses: chain.iter().map(|i| alses[*i].clone()).collect(),
+            rcc_values: rccs.to_vec(),
+            remove_candidates,
+        };
+        acc.add_step(Step::Als(step));
+    }
+}
+
+impl SolverStrategy for AlsChainFinder {
+    fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        self.find_als_chain(grid, acc);
+    }
+    fn name(&self) -> &str {
+        "AlsChainFinder"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        grid::Grid,
+        solver::{
+            SolverStrategy, als::als_chain::AlsChainFinder, step::Step,
+            step_accumulator::SingleStepAccumulator,
+        },
+    };
+
+    #[test]
+    fn test_als_chain() {
+        let s = r#".----------------.---------------.------------.
+| 4   8      7   | 3   12    12  | 56  9   56 |
+| 59  59     3   | 6   48    48  | 2   7   1  |
+| 1   2      6   | 57  9     57  | 3   8   4  |
+:----------------+---------------+------------:
+| 7   34     5   | 89  348   489 | 1   6   2  |
+| 69  13469  49  | 2   1346  57  | 8   34  57 |
+| 28  1346   28  | 57  1346  14  | 57  34  9  |
+:----------------+---------------+------------:
+| 58  45     1   | 48  7     6   | 9   2   3  |
+| 3   67     89  | 1   28    289 | 4   5   67 |
+| 26  4679   249 | 49  5     3   | 67  1   8  |
+'----------------'---------------'------------'"#;
+        let solution =
+            "487312695593684271126597384735849162914265837268731549851476923379128456642953718";
+        let solution: Vec<u8> = solution.bytes().map(|b| b - b'0').collect();
+        let mut grid = Grid::new_from_matrix_str(s).unwrap();
+        let solver = AlsChainFinder::default();
+        let mut acc = SingleStepAccumulator::default();
+        solver.find_step(&grid, &mut acc);
+        let step = acc.get_step();
+        match step {
+            Step::Als(als) => assert!(als.alses.len() >= 4),
+            _ => panic!("no als chain found"),
+        }
+        step.apply(&mut grid);
+        assert!(grid.check_grid_valid(&solution));
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    solver::{
+        SolverStrategy,
+        als::{AlsStep, AlsType, find_all_als, find_eliminations, find_rcc_links},
+        step::Step,
+        step_accumulator::StepAccumulator,
+    },
+};
+
+#[derive(Default)]
+pub struct AlsXyWingFinder {}
+
+impl AlsXyWingFinder {
+    pub fn find_als_xy_wing(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        let alses = find_all_als(grid);
+        let links = find_rcc_links(&alses);
+        // c is the pivot, a and b are the wings
+        for (c, c_links) in links.iter().enumerate() {
+            for (i, (a, rccs_a)) in c_links.iter().enumerate() {
+                for (b, rccs_b) in c_links.iter().skip(i + 1) {
+                    let als_a = &alses[*a];
+                    let als_b = &alses[*b];
+                    if als_a.is_overlap(als_b) {
+                        continue;
+                    }
+                    for x in rccs_a.iter() {
+                        for y in rccs_b.iter() {
+                            if x == y {
+                                continue;
+                            }
+                            let mut remove_candidates: Vec<Candidate> = Vec::new();
+                            for z in als_a.values.intersect(&als_b.values).iter() {
+                                if z == x || z == y {
+                                    continue;
+                                }
+                                remove_candidates.extend(find_eliminations(
+                                    grid,
+                                    &[als_a, als_b],
+                                    z,
+                                ));
+                            }
+                            if remove_candidates.is_empty() {
+                                continue;
+                            }
+                            let step = AlsStep {
+                                als_type: AlsType::XyWing,
+                                alses: vec![als_a.clone(), alses[c].clone(), als_b.clone()],
+                                rcc_values: vec![x, y],
+                                remove_candidates,
+                            };
+                            if acc.add_step(Step::Als(step)) {
+                                return;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl SolverStrategy for AlsXyWingFinder {
+    fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        self.find_als_xy_wing(grid, acc);
+    }
+    fn name(&self) -> &str {
+        "AlsXyWingFinder"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        candidate::Candidate,
+        grid::Grid,
+        solver::{
+            SolverStrategy, als::als_xy_wing::AlsXyWingFinder, step::Step,
+            step_accumulator::AllStepAccumulator,
+        },
+    };
+
+    #[test]
+    fn test_als_xy_wing() {
+        let s = r#".--------------------.------------------.--------------.
+| 59     6     2     | 3   1      4     | 8    59   7  |
+| 14589  3     4589  | 58  6789   5679  | 2    16   59 |
+| 1589   7     589   | 2   689    569   | 4    16   3  |
+:--------------------+------------------+--------------:
+| 4578   2458  4578  | 9   24678  12567 | 57   3    16 |
+| 6      589   1     | 58  378    357   | 579  4    2  |
+| 34579  2459  34579 | 14  2467   12567 | 579  8    16 |
+:--------------------+------------------+--------------:
+| 2      58    578   | 6   39     39    | 1    57   4  |
+| 347    49    3479  | 14  5      12    | 6    279  8  |
+| 459    1     6     | 7   24     8     | 3    259  59 |
+'--------------------'------------------'--------------'"#;
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let solver = AlsXyWingFinder::default();
+        let mut acc = AllStepAccumulator::default();
+        solver.find_step(&grid, &mut acc);
+        let steps = acc.get_steps();
+        assert_eq!(steps.len(), 20);
+        // pivot r1c1 {5,9}, 4 removed from r8c3
+        assert!(steps.iter().any(|step| match step {
+            Step::Als(als) => als.remove_candidates == vec![Candidate::new(65, 4)],
+            _ => false,
+        }));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    solver::{
+        SolverStrategy,
+        als::{AlsStep, AlsType, find_all_als, find_eliminations, restricted_commons},
+        step::Step,
+        step_accumulator::StepAccumulator,
+    },
+};
+
+#[derive(Default)]
+pub struct AlsXzFinder {}
+
+impl AlsXzFinder {
+    pub fn find_als_xz(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        let alses = find_all_als(grid);
+        for i in 0..alses.len() {
+            for j in (i + 1)..alses.len() {
+                let a = &alses[i];
+                let b = &alses[j];
+                let rccs = restricted_commons(a, b);
+                if rccs.is_empty() || rccs.count() > 2 {
+                    continue;
+                }
+                let mut remove_candidates: Vec<Candidate> = Vec::new();
+                for z in a.values.intersect(&b.values).iter() {
+                    if rccs.contains(z) {
+                        continue;
+                    }
+                    remove_candidates.extend(find_eliminations(grid, &[a, b], z));
+                }
+                if rccs.count() == 2 {
+                    // doubly linked: both alses become locked sets, every rcc is locked between
+                    // them and every other value is locked in its own als
+                    for x in rccs.iter() {
+                        remove_candidates.extend(find_eliminations(grid, &[a, b], x));
+                    }
+                    for als in [a, b] {
+                        for v in als.values.difference(&rccs).iter() {
+                            remove_candidates.extend(find_eliminations(grid, &[als], v));
+                        }
+                    }
+                }
+                remove_candidates.sort_by_key(|c| (c.cell(), c.value()));
+                remove_candidates.dedup();
+                if remove_candidates.is_empty() {
+                    continue;
+                }
+                let step = AlsStep {
+                    als_type: AlsType::Xz,
+                    alses: vec![a.clone(), b.clone()],
+                    rcc_values: rccs.values(),
+                    remove_candidates,
+                };
+                if acc.add_step(Step::Als(step)) {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl SolverStrategy for AlsXzFinder {
+    fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        self.find_als_xz(grid, acc);
+    }
+    fn name(&self) -> &str {
+        "AlsXzFinder"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        candidate::Candidate,
+        grid::Grid,
+        solver::{
+            SolverStrategy, als::als_xz::AlsXzFinder, step::Step,
+            step_accumulator::AllStepAccumulator,
+        },
+    };
+
+    #[test]
+    fn test_als_xz() {
+        let s = r#".-----------------.---------------.------------.
+| 4    8      7   | 3   12    12  | 56  9   56 |
+| 59   359    39  | 6   48    48  | 2   7   1  |
+| 1    2      6   | 57  9     57  | 3   8   4  |
+:-----------------+---------------+------------:
+| 7    349    5   | 89  348   489 | 1   6   2  |
+| 69   13469  349 | 2   1346  57  | 8   34  57 |
+| 28   1346   28  | 57  1346  14  | 57  34  9  |
+:-----------------+---------------+------------:
+| 58   45     1   | 48  7     6   | 9   2   3  |
+| 3    67     89  | 1   28    289 | 4   5   67 |
+| 269  4679   249 | 49  5     3   | 67  1   8  |
+'-----------------'---------------'------------'"#;
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let solver = AlsXzFinder::default();
+        let mut acc = AllStepAccumulator::default();
+        solver.find_step(&grid, &mut acc);
+        let steps = acc.get_steps();
+        assert_eq!(steps.len(), 109);
+        // r2c1 {5,9} and r7c1,r8c3 {5,8,9} linked by 5, 9 removed from r2c3 and r9c1
+        let expected = vec![Candidate::new(11, 9), Candidate::new(72, 9)];
+        assert!(steps.iter().any(|step| match step {
+            Step::Als(als) => als.remove_candidates == expected,
+            _ => false,
+        }));
+    }
+}
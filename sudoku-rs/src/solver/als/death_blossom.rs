@@ -0,0 +1,273 @@
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    grid_constant::get_cell_buddies,
+    solver::{
+        SolverStrategy,
+        als::{Als, find_all_als, find_eliminations},
+        step::Step,
+        step_accumulator::StepAccumulator,
+    },
+    util::{
+        digitset::DigitSet,
+        format_step::{format_candidates_cells, format_candidates_values},
+        indexset::IndexSet,
+    },
+};
+
+// the als linked to one candidate of the stem, all its cells with the value see the stem
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Petal {
+    pub value: u8,
+    pub als: Als,
+}
+
+/**
+Death Blossom: whatever value the stem has, the petal linked to it loses that value and becomes a
+locked set. a value z common to all petals must then be true in one of them, so z can be removed
+from cells which see all z cells of the petals (and the stem when it has z too)
+**/
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeathBlossom {
+    pub stem: Vec<Candidate>,
+    pub petals: Vec<Petal>,
+    pub remove_candidates: Vec<Candidate>,
+}
+
+impl DeathBlossom {
+    pub fn apply(&self, grid: &mut Grid) {
+        for cand in self.remove_candidates.iter() {
+            grid.remvoe_candidate(cand);
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        "Death Blossom"
+    }
+
+    pub fn difficulty(&self) -> u32 {
+        360
+    }
+
+    pub fn explain(&self) -> String {
+        let mut res = format!(
+            "<h3>{}</h3><p>stem {} ({})<br>",
+            self.name(),
+            format_candidates_cells(&self.stem),
+            format_candidates_values(&self.stem)
+        );
+        for petal in self.petals.iter() {
+            res.push_str(format!("<b>{}</b>: {}<br>", petal.value, petal.als.format()).as_str());
+        }
+        res.push_str(
+            format!(
+                "one of the petals must have {}, it can be removed from {}</p>",
+                format_candidates_values(&self.remove_candidates),
+                format_candidates_cells(&self.remove_candidates)
+            )
+            .as_str(),
+        );
+        res
+    }
+}
+
+#[derive(Default)]
+pub struct DeathBlossomFinder {}
+
+struct Blossom<'a> {
+    stem: u8,
+    values: Vec<u8>,
+    // the index of the alses which can be the petal of every stem value
+    candidates: Vec<Vec<usize>>,
+    alses: &'a [Als],
+}
+
+impl DeathBlossomFinder {
+    // the alses without the stem whose cells with the value all see the stem
+    fn petal_candidates(&self, alses: &[Als], stem: u8, value: u8) -> Vec<usize> {
+        let buddies = get_cell_buddies(stem);
+        alses
+            .iter()
+            .enumerate()
+            .filter(|(_, als)| {
+                als.values.contains(value)
+                    && !als.cells.contains(stem)
+                    && buddies.intersect(&als.value_cells(value)) == als.value_cells(value)
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    // petals are chosen value by value, they don't overlap and have at least one common value.
+    // true when the accumulator is full
+    fn find_petals(
+        &self,
+        grid: &Grid,
+        blossom: &Blossom,
+        petals: &mut Vec<usize>,
+        cells: IndexSet,
+        common: DigitSet,
+        acc: &mut dyn StepAccumulator,
+    ) -> bool {
+        let index = petals.len();
+        if index == blossom.values.len() {
+            return self.check_blossom(grid, blossom, petals, &common, acc);
+        }
+        for i in blossom.candidates[index].iter() {
+            let als = &blossom.alses[*i];
+            if !als.cells.intersect(&cells).is_empty() {
+                continue;
+            }
+            let common = common.intersect(&als.values);
+            if common.is_empty() {
+                continue;
+            }
+            petals.push(*i);
+            if self.find_petals(grid, blossom, petals, cells.union(&als.cells), common, acc) {
+                return true;
+            }
+            petals.pop();
+        }
+        false
+    }
+
+    fn check_blossom(
+        &self,
+        grid: &Grid,
+        blossom: &Blossom,
+        petals: &[usize],
+        common: &DigitSet,
+        acc: &mut dyn StepAccumulator,
+    ) -> bool {
+        let alses: Vec<&Als> = petals.iter().map(|i| &blossom.alses[*i]).collect();
+        let mut remove_candidates = Vec::new();
+        for z in common.iter() {
+            let mut eliminations = find_eliminations(grid, &alses, z);
+            if grid.cell_has_candidate(blossom.stem, z) {
+                let buddies = get_cell_buddies(blossom.stem);
+                eliminations.retain(|c| buddies.contains(c.cell()));
+            }
+            remove_candidates.extend(eliminations);
+        }
+        if remove_candidates.is_empty() {
+            return false;
+        }
+        remove_candidates.sort_by_key(|c| (c.cell(), c.value()));
+        let step = DeathBlossom {
+            stem: blossom
+                .values
+                .iter()
+                .map(|v| Candidate::new(blossom.stem, *v))
+                .collect(),
+            petals: blossom
+                .values
+                .iter()
+                .zip(alses)
+                .map(|(v, als)| Petal {
+                    value: *v,
+                    als: als.clone(),
+                })
+                .collect(),
+            remove_candidates,
+        };
+        acc.add_step(Step::DeathBlossom(step))
+    }
+
+    pub fn find_death_blossom(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        let alses = find_all_als(grid);
+        for stem in 0..81_u8 {
+            let values = grid.get_cell_candidate(stem).values();
+            if values.len() < 2 {
+                continue;
+            }
+            let candidates: Vec<Vec<usize>> = values
+                .iter()
+                .map(|v| self.petal_candidates(&alses, stem, *v))
+                .collect();
+            if candidates.iter().any(|c| c.is_empty()) {
+                continue;
+            }
+            let blossom = Blossom {
+                stem,
+                values,
+                candidates,
+                alses: &alses,
+            };
+            let mut petals = Vec::new();
+            if self.find_petals(
+                grid,
+                &blossom,
+                &mut petals,
+                IndexSet::new_empty(),
+                DigitSet::new_full(),
+                acc,
+            ) {
+                return;
+            }
+        }
+    }
+}
+
+impl SolverStrategy for DeathBlossomFinder {
+    fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        self.find_death_blossom(grid, acc);
+    }
+    fn name(&self) -> &str {
+        "DeathBlossomFinder"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        candidate::Candidate,
+        grid::Grid,
+        solver::{
+            SolverStrategy, als::death_blossom::DeathBlossomFinder, bitboard::BitboardSolver,
+            step::Step, step_accumulator::AllStepAccumulator,
+        },
+    };
+
+    #[test]
+    fn test_death_blossom() {
+        let s = ".-----------.---------------.------------------.
+| 4   39  2 | 7   13   1358 | 1569   1589  136 |
+| 8   39  7 | 15  4    6    | 1259   1259  123 |
+| 5   6   1 | 9   23   238  | 4      28    7   |
+:-----------+---------------+------------------:
+| 29  1   3 | 6   79   279  | 8      4     5   |
+| 29  7   8 | 4   5    129  | 3      6     12  |
+| 6   5   4 | 8   123  123  | 12     7     9   |
+:-----------+---------------+------------------:
+| 7   8   5 | 2   6    19   | 19     3     4   |
+| 1   2   6 | 3   79   4    | 579    59    8   |
+| 3   4   9 | 15  8    157  | 12567  125   126 |
+'-----------'---------------'------------------'";
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let solution = *BitboardSolver::new().solve(&grid).values();
+        let mut acc = AllStepAccumulator::default();
+        DeathBlossomFinder::default().find_step(&grid, &mut acc);
+        let steps = acc.get_steps();
+        assert!(!steps.is_empty());
+        let mut found = false;
+        for step in steps.iter() {
+            let Step::DeathBlossom(blossom) = step else {
+                panic!("not death blossom step");
+            };
+            assert_eq!(blossom.stem.len(), blossom.petals.len());
+            for cand in blossom.remove_candidates.iter() {
+                assert_ne!(solution[cand.cell() as usize], cand.value());
+            }
+            // stem r3c6 (2,3,8) has three petals, all of them and the stem see r1c6 with 3
+            if blossom.stem[0].cell() == 23
+                && blossom.petals.len() == 3
+                && blossom.remove_candidates == vec![Candidate::new(5, 3)]
+            {
+                found = true;
+            }
+        }
+        assert!(found);
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::HashSet;
+
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    grid_constant::get_common_buddies,
+    util::{
+        create_permutations,
+        digitset::DigitSet,
+        format_step::{format_candidates_cells, format_candidates_values, format_house},
+        indexset::IndexSet,
+    },
+};
+
+pub mod als_chain;
+pub mod als_xy_wing;
+pub mod als_xz;
+pub mod death_blossom;
+
+/**
+Almost Locked Set: n cells in one house which have n+1 candidates together.
+a single bivalue cell is also an als.
+**/
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Als {
+    pub house: u8,
+    pub cells: IndexSet,
+    pub values: DigitSet,
+    pub candidates: Vec<Candidate>,
+}
+
+impl Als {
+    pub fn new(grid: &Grid, house: u8, cells: IndexSet) -> Self {
+        let mut values = DigitSet::new_empty();
+        let mut candidates = Vec::new();
+        for cell in cells.iter() {
+            let cell_candidate = grid.get_cell_candidate(cell);
+            values = values.union(&cell_candidate);
+            for v in cell_candidate.iter() {
+                candidates.push(Candidate::new(cell, v));
+            }
+        }
+        Als {
+            house,
+            cells,
+            values,
+            candidates,
+        }
+    }
+
+    // cells of the als which have candidate value
+    pub fn value_cells(&self, value: u8) -> IndexSet {
+        IndexSet::new_from_values(
+            self.candidates
+                .iter()
+                .filter(|c| c.value() == value)
+                .map(|c| c.cell()),
+        )
+    }
+
+    pub fn is_overlap(&self, other: &Als) -> bool {
+        !self.cells.intersect(&other.cells).is_empty()
+    }
+
+    pub fn format(&self) -> String {
+        format!(
+            "{} ({}) in {}",
+            format_candidates_cells(&self.candidates),
+            format_candidates_values(&self.candidates),
+            format_house(self.house)
+        )
+    }
+}
+
+// all als in grid, same cells found in different houses only keep once
+pub fn find_all_als(grid: &Grid) -> Vec<Als> {
+    let mut alses = Vec::new();
+    let mut used: HashSet<IndexSet> = HashSet::new();
+    for house in 0..27 {
+        let empty_cells = grid.house_empty_cells(house).values();
+        for degree in 1..empty_cells.len() {
+            for cells in create_permutations(empty_cells.clone(), degree as u8) {
+                let values = cells.iter().fold(DigitSet::new_empty(), |u, cell| {
+                    u.union(&grid.get_cell_candidate(*cell))
+                });
+                if values.count() as usize != degree + 1 {
+                    continue;
+                }
+                let cell_set = IndexSet::new_from_values(cells.into_iter());
+                if used.contains(&cell_set) {
+                    continue;
+                }
+                used.insert(cell_set);
+                alses.push(Als::new(grid, house, cell_set));
+            }
+        }
+    }
+    alses
+}
+
+// restricted common candidates: all cells with value in both als see each other, so value can
+// only be true in one of them
+pub fn restricted_commons(a: &Als, b: &Als) -> DigitSet {
+    let mut rccs = DigitSet::new_empty();
+    if a.is_overlap(b) {
+        return rccs;
+    }
+    for v in a.values.intersect(&b.values).iter() {
+        let a_cells = a.value_cells(v);
+        let b_cells = b.value_cells(v);
+        if get_common_buddies(&a_cells).intersect(&b_cells) == b_cells {
+            rccs.add(v);
+        }
+    }
+    rccs
+}
+
+// for every als, the index of alses linked to it by at least one restricted common candidate
+pub fn find_rcc_links(alses: &[Als]) -> Vec<Vec<(usize, DigitSet)>> {
+    let mut links = vec![Vec::new(); alses.len()];
+    for i in 0..alses.len() {
+        for j in (i + 1)..alses.len() {
+            let rccs = restricted_commons(&alses[i], &alses[j]);
+            if rccs.is_empty() {
+                continue;
+            }
+            links[i].push((j, rccs));
+            links[j].push((i, rccs));
+        }
+    }
+    links
+}
+
+// candidates with value outside all alses which see every value cell of them
+pub fn find_eliminations(grid: &Grid, alses: &[&Als], value: u8) -> Vec<Candidate> {
+    let value_cells = alses
+        .iter()
+        .fold(IndexSet::new_empty(), |u, als| u.union(&als.value_cells(value)));
+    let als_cells = alses
+        .iter()
+        .fold(IndexSet::new_empty(), |u, als| u.union(&als.cells));
+    if value_cells.is_empty() {
+        return Vec::new();
+    }
+    get_common_buddies(&value_cells)
+        .difference(&als_cells)
+        .iter()
+        .filter(|cell| grid.cell_has_candidate(*cell, value))
+        .map(|cell| Candidate::new(cell, value))
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AlsType {
+    Xz,
+    XyWing,
+    Chain,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlsStep {
+    pub als_type: AlsType,
+    pub alses: Vec<Als>,
+    // restricted common candidates between neighboring alses, a doubly linked ALS-XZ has two
+    pub rcc_values: Vec<u8>,
+    pub remove_candidates: Vec<Candidate>,
+}
+
+impl AlsStep {
+    pub fn apply(&self, grid: &mut Grid) {
+        for cand in self.remove_candidates.iter() {
+            grid.remvoe_candidate(cand);
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self.als_type {
+            AlsType::Xz => "ALS-XZ",
+            AlsType::XyWing => "ALS-XY-Wing",
+            AlsType::Chain => "ALS-Chain",
+        }
+    }
+
+    pub fn difficulty(&self) -> u32 {
+        match self.als_type {
+            AlsType::Xz => 300,
+            AlsType::XyWing => 320,
+            AlsType::Chain => 340,
+        }
+    }
+
+    pub fn explain(&self) -> String {
+        let mut res = format!("<h3>{}</h3><p>", self.name());
+        for (i, als) in self.alses.iter().enumerate() {
+            res.push_str(format!("ALS {}: {}<br>", i + 1, als.format()).as_str());
+        }
+        let rccs: Vec<String> = self
+            .rcc_values
+            .iter()
+            .map(|v| format!("<b>{}</b>", v))
+            .collect();
+        res.push_str(
+            format!(
+                "linked by restricted common {}, {} can be removed from {}</p>",
+                rccs.join(","),
+                format_candidates_values(&self.remove_candidates),
+                format_candidates_cells(&self.remove_candidates)
+            )
+            .as_str(),
+        );
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::{
+        grid::Grid,
+        solver::als::{find_all_als, restricted_commons},
+        util::{digitset::DigitSet, indexset::IndexSet},
+    };
+
+    #[test]
+    fn test_find_all_als() {
+        let s = r#".--------------------.------------------.--------------.
+| 59     6     2     | 3   1      4     | 8    59   7  |
+| 14589  3     4589  | 58  6789   5679  | 2    16   59 |
+| 1589   7     589   | 2   689    569   | 4    16   3  |
+:--------------------+------------------+--------------:
+| 4578   2458  4578  | 9   24678  12567 | 57   3    16 |
+| 6      589   1     | 58  378    357   | 579  4    2  |
+| 34579  2459  34579 | 14  2467   12567 | 579  8    16 |
+:--------------------+------------------+--------------:
+| 2      58    578   | 6   39     39    | 1    57   4  |
+| 347    49    3479  | 14  5      12    | 6    279  8  |
+| 459    1     6     | 7   24     8     | 3    259  59 |
+'--------------------'------------------'--------------'"#;
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let alses = find_all_als(&grid);
+        let mut cell_sets = HashSet::new();
+        for als in alses.iter() {
+            assert_eq!(als.values.count(), als.cells.count() + 1);
+            assert!(cell_sets.insert(als.cells));
+        }
+        // r1c1 {5,9} and r1c8 {5,9} see each other, both digits are restricted common
+        let r1c1 = alses
+            .iter()
+            .find(|als| als.cells == IndexSet::new_from_values([0].into_iter()))
+            .unwrap();
+        let r1c8 = alses
+            .iter()
+            .find(|als| als.cells == IndexSet::new_from_values([7].into_iter()))
+            .unwrap();
+        assert_eq!(
+            restricted_commons(r1c1, r1c8),
+            DigitSet::new_from_values(&[5, 9])
+        );
+    }
+}
@@ -9,6 +9,7 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AvoidableRectangleType1 {
     pub remove_candidates: Vec<Candidate>,
     pub highlight_candidates: Vec<Candidate>,
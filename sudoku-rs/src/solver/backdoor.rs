@@ -0,0 +1,217 @@
+use std::fmt;
+
+use crate::{
+    candidate::Candidate,
+    error::{Result, SudokuError},
+    grid::Grid,
+    solution::SolutionState,
+    solver::{SimpleSolver, bitboard::BitboardSolver},
+    util::format_step::format_cell,
+};
+
+// a guess which agrees with the solution: a value placed or a false candidate eliminated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Guess {
+    Place(Candidate),
+    Eliminate(Candidate),
+}
+
+impl Guess {
+    pub fn apply(&self, grid: &mut Grid) {
+        match self {
+            Guess::Place(cand) => {
+                grid.set_value_with_candidate(cand);
+            }
+            Guess::Eliminate(cand) => grid.remvoe_candidate(cand),
+        }
+    }
+
+    // the guess is already done in the grid
+    fn is_resolved(&self, grid: &Grid) -> bool {
+        match self {
+            Guess::Place(cand) => grid.get_value(cand.cell()) != 0,
+            Guess::Eliminate(cand) => !grid.cell_has_candidate(cand.cell(), cand.value()),
+        }
+    }
+}
+
+impl fmt::Display for Guess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Guess::Place(cand) => write!(f, "{}={}", format_cell(cand.cell()), cand.value()),
+            Guess::Eliminate(cand) => write!(f, "{}<>{}", format_cell(cand.cell()), cand.value()),
+        }
+    }
+}
+
+/**
+the smallest sets of guesses which let the strategies of the solver finish the grid.
+size is 0 when the solver finishes the grid without guess, None when there is no backdoor up to
+the max size. every backdoor has size guesses
+**/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackdoorReport {
+    pub size: Option<usize>,
+    pub backdoors: Vec<Vec<Guess>>,
+}
+
+// backdoors of size 1 and 2 are searched, max_size greater than 2 is treated as 2
+pub fn find_backdoors(
+    solver: &SimpleSolver,
+    grid: &Grid,
+    max_size: usize,
+) -> Result<BackdoorReport> {
+    search(solver, grid, max_size, false)
+}
+
+// the search stops at the first backdoor, much faster than find_backdoors when only the size is
+// needed
+pub fn backdoor_size(solver: &SimpleSolver, grid: &Grid, max_size: usize) -> Result<Option<usize>> {
+    Ok(search(solver, grid, max_size, true)?.size)
+}
+
+fn search(
+    solver: &SimpleSolver,
+    grid: &Grid,
+    max_size: usize,
+    first_only: bool,
+) -> Result<BackdoorReport> {
+    let solution = BitboardSolver::new().solve(grid);
+    if solution.state() != &SolutionState::Unique {
+        return Err(SudokuError::InvalidInput(format!(
+            "backdoor needs unique solution, got {:?}",
+            solution.state()
+        )));
+    }
+    let solution = *solution.values();
+    let solves = |grid: &Grid| {
+        let mut grid = grid.clone();
+        solver.solve(&mut grid);
+        grid.is_solved() && grid.values() == &solution
+    };
+
+    // the guesses start from where the solver gets stuck
+    let mut stuck = grid.clone();
+    solver.solve(&mut stuck);
+    if stuck.is_solved() {
+        return Ok(BackdoorReport {
+            size: Some(0),
+            backdoors: vec![],
+        });
+    }
+    let guesses = solution_guesses(&stuck, &solution);
+    let mut report = BackdoorReport {
+        size: None,
+        backdoors: vec![],
+    };
+    if max_size == 0 {
+        return Ok(report);
+    }
+
+    // the grid after every guess and the strategies, the pairs start from it
+    let mut guessed = Vec::with_capacity(guesses.len());
+    for guess in guesses.iter() {
+        let mut grid = stuck.clone();
+        guess.apply(&mut grid);
+        solver.solve(&mut grid);
+        if grid.is_solved() && grid.values() == &solution {
+            report.backdoors.push(vec![*guess]);
+            if first_only {
+                break;
+            }
+        }
+        guessed.push(grid);
+    }
+    if !report.backdoors.is_empty() {
+        report.size = Some(1);
+        return Ok(report);
+    }
+    if max_size == 1 {
+        return Ok(report);
+    }
+
+    for (i, first) in guesses.iter().enumerate() {
+        for second in guesses[i + 1..].iter() {
+            if second.is_resolved(&guessed[i]) {
+                continue;
+            }
+            let mut grid = guessed[i].clone();
+            second.apply(&mut grid);
+            if solves(&grid) {
+                report.backdoors.push(vec![*first, *second]);
+                if first_only {
+                    report.size = Some(2);
+                    return Ok(report);
+                }
+            }
+        }
+    }
+    if !report.backdoors.is_empty() {
+        report.size = Some(2);
+    }
+    Ok(report)
+}
+
+// the values of the empty cells and the false candidates, the values are tried first as they
+// are more likely to be a backdoor
+fn solution_guesses(grid: &Grid, solution: &[u8; 81]) -> Vec<Guess> {
+    let mut guesses = Vec::new();
+    let mut eliminations = Vec::new();
+    for cell in 0..81 {
+        if grid.get_value(cell) != 0 {
+            continue;
+        }
+        for value in grid.get_cell_candidate(cell).iter() {
+            let cand = Candidate::new(cell, value);
+            if value == solution[cell as usize] {
+                guesses.push(Guess::Place(cand));
+            } else {
+                eliminations.push(Guess::Eliminate(cand));
+            }
+        }
+    }
+    guesses.extend(eliminations);
+    guesses
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        grid::Grid,
+        solver::{
+            SimpleSolver,
+            backdoor::{Guess, backdoor_size, find_backdoors},
+            config::SolverConfig,
+        },
+    };
+
+    #[test]
+    fn test_find_backdoors() {
+        let solver = SimpleSolver::from_config(&SolverConfig::beginner()).unwrap();
+        let s = "1.....569492.561.8.561.924...964.8.1.64.1....218.356.4.4.5...169.5.614.2621.....5";
+        let grid = Grid::new_from_singline_digit(s).unwrap();
+        let report = find_backdoors(&solver, &grid, 2).unwrap();
+        assert_eq!(report.size, Some(1));
+        assert!(!report.backdoors.is_empty());
+        for backdoor in report.backdoors.iter() {
+            let mut grid = grid.clone();
+            solver.solve(&mut grid);
+            backdoor[0].apply(&mut grid);
+            solver.solve(&mut grid);
+            assert!(grid.is_solved());
+        }
+        assert_eq!(backdoor_size(&solver, &grid, 2).unwrap(), Some(1));
+        assert!(
+            report
+                .backdoors
+                .iter()
+                .any(|b| matches!(b[0], Guess::Place(_)))
+        );
+
+        let easy =
+            "...16.87..1.875..38.73..651.5.62173...17..5.473.5..1...7........8.256917.62..7...";
+        let grid = Grid::new_from_singline_digit(easy).unwrap();
+        let report = find_backdoors(&SimpleSolver::new(), &grid, 2).unwrap();
+        assert_eq!(report.size, Some(0));
+    }
+}
@@ -0,0 +1,301 @@
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    grid_constant::{get_cell_buddies, get_common_buddies, get_house_cell_set},
+    solver::{SolverStrategy, step::Step, step_accumulator::StepAccumulator},
+    util::{
+        create_permutations,
+        digitset::DigitSet,
+        format_step::{format_candidates_cells, format_candidates_values},
+        indexset::IndexSet,
+    },
+};
+
+/**
+bent naked subset: n cells with n candidates which are not in one house, the pivot sees all the
+pincers. every value but z can only be true once in the cells because all its cells see each
+other, so z must be true in one of its cells, z can be removed from cells which see all of them.
+size 3 is XYZ-Wing, 4 is WXYZ-Wing and 5 is VWXYZ-Wing
+**/
+
+#[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BentSet {
+    pub size: u8,
+    pub value: u8,
+    pub pivot: Vec<Candidate>,
+    pub pincers: Vec<Candidate>,
+    pub remove_candidates: Vec<Candidate>,
+}
+
+impl BentSet {
+    pub fn apply(&self, grid: &mut Grid) {
+        for cand in self.remove_candidates.iter() {
+            grid.remvoe_candidate(cand);
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        wing_name(self.size)
+    }
+
+    pub fn difficulty(&self) -> u32 {
+        match self.size {
+            3 => 180,
+            4 => 200,
+            _ => 220,
+        }
+    }
+
+    pub fn explain(&self) -> String {
+        format!(
+            "<h3>{}</h3><p>pivot {} ({}) sees pincers {}, all values except <b>{}</b> can only be true once, so {} can be removed from {}</p>",
+            self.name(),
+            format_candidates_cells(&self.pivot),
+            format_candidates_values(&self.pivot),
+            format_candidates_cells(&self.pincers),
+            self.value,
+            format_candidates_values(&self.remove_candidates),
+            format_candidates_cells(&self.remove_candidates)
+        )
+    }
+}
+
+fn wing_name(size: u8) -> &'static str {
+    match size {
+        3 => "XYZ-Wing",
+        4 => "WXYZ-Wing",
+        _ => "VWXYZ-Wing",
+    }
+}
+
+pub struct BentSetFinder {
+    size: u8,
+}
+
+impl BentSetFinder {
+    pub fn new(size: u8) -> Self {
+        BentSetFinder { size }
+    }
+
+    fn cell_candidates(&self, grid: &Grid, cells: &IndexSet) -> Vec<Candidate> {
+        let mut candidates = Vec::new();
+        for cell in cells.iter() {
+            for v in grid.get_cell_candidate(cell).iter() {
+                candidates.push(Candidate::new(cell, v));
+            }
+        }
+        candidates
+    }
+
+    // the cell sees all other cells of the set
+    fn sees_all(&self, cell: u8, cells: &IndexSet) -> bool {
+        let mut others = *cells;
+        others.remove(cell);
+        get_cell_buddies(cell).intersect(&others) == others
+    }
+
+    // the only value whose cells don't see each other
+    fn find_unrestricted_value(
+        &self,
+        grid: &Grid,
+        cells: &IndexSet,
+        values: &DigitSet,
+    ) -> Option<u8> {
+        let mut unrestricted = None;
+        for v in values.iter() {
+            let value_cells =
+                IndexSet::new_from_values(cells.iter().filter(|c| grid.cell_has_candidate(*c, v)));
+            if value_cells.iter().all(|c| self.sees_all(c, &value_cells)) {
+                continue;
+            }
+            if unrestricted.is_some() {
+                return None;
+            }
+            unrestricted = Some(v);
+        }
+        unrestricted
+    }
+
+    pub fn find_bent_set(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        for pivot in 0..81_u8 {
+            let pivot_values = grid.get_cell_candidate(pivot);
+            // a bivalue pivot of size 3 is the XY-Wing
+            let min_count = if self.size == 3 { 3 } else { 2 };
+            if pivot_values.count() < min_count || pivot_values.count() > self.size {
+                continue;
+            }
+            let buddies: Vec<u8> = get_cell_buddies(pivot)
+                .iter()
+                .filter(|c| {
+                    let count = grid.get_cell_candidate(*c).count();
+                    count >= 2 && count <= self.size
+                })
+                .collect();
+            if buddies.len() < (self.size - 1) as usize {
+                continue;
+            }
+            for pincers in create_permutations(buddies, self.size - 1) {
+                let values = pincers
+                    .iter()
+                    .fold(pivot_values, |u, c| u.union(&grid.get_cell_candidate(*c)));
+                if values.count() != self.size {
+                    continue;
+                }
+                let pincer_cells = IndexSet::new_from_values(pincers.into_iter());
+                let mut cells = pincer_cells;
+                cells.add(pivot);
+                // in one house it is a naked set
+                if (0..27).any(|h| get_house_cell_set(h).intersect(&cells) == cells) {
+                    continue;
+                }
+                // every cell seeing all others can be the pivot, only keep the first one
+                if cells.iter().any(|c| c < pivot && self.sees_all(c, &cells)) {
+                    continue;
+                }
+                let Some(z) = self.find_unrestricted_value(grid, &cells, &values) else {
+                    continue;
+                };
+                let z_cells = IndexSet::new_from_values(
+                    cells.iter().filter(|c| grid.cell_has_candidate(*c, z)),
+                );
+                let remove_candidates: Vec<Candidate> = get_common_buddies(&z_cells)
+                    .difference(&cells)
+                    .iter()
+                    .filter(|c| grid.cell_has_candidate(*c, z))
+                    .map(|c| Candidate::new(c, z))
+                    .collect();
+                if remove_candidates.is_empty() {
+                    continue;
+                }
+                let step = BentSet {
+                    size: self.size,
+                    value: z,
+                    pivot: self
+                        .cell_candidates(grid, &IndexSet::new_from_values([pivot].into_iter())),
+                    pincers: self.cell_candidates(grid, &pincer_cells),
+                    remove_candidates,
+                };
+                if acc.add_step(Step::BentSet(step)) {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl SolverStrategy for BentSetFinder {
+    fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        self.find_bent_set(grid, acc);
+    }
+
+    fn name(&self) -> &str {
+        wing_name(self.size)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        candidate::Candidate,
+        grid::Grid,
+        solver::{
+            SolverStrategy, bent_set::BentSetFinder, bitboard::BitboardSolver, step::Step,
+            step_accumulator::AllStepAccumulator,
+        },
+    };
+
+    #[test]
+    pub fn test_xyz_wing() {
+        let s = r#".----------------.-----------------.---------------.
+| 5   2     7    | 3  1      6     | 49   8   49   |
+| 8   69    69   | 5  24     24    | 7    3   1    |
+| 3   1     4    | 9  78     78    | 25   6   25   |
+:----------------+-----------------+---------------:
+| 1   579   2359 | 4  579    3579  | 8    79  6    |
+| 6   789   9    | 2  789    1789  | 3    5   479  |
+| 4   5789  2359 | 6  5789   35789 | 2    1   279  |
+:----------------+-----------------+---------------:
+| 29  4     1    | 8  2569   259   | 56   79  3579 |
+| 7   56    56   | 1  3      49    | 49   2   8    |
+| 29  3     8    | 7  24569  2459  | 156  4   459  |
+'----------------'-----------------'---------------'"#;
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let finder = BentSetFinder::new(3);
+        let mut acc = AllStepAccumulator::default();
+        finder.find_step(&grid, &mut acc);
+        assert_eq!(acc.get_steps().len(), 1);
+        assert_eq!(acc.get_steps().iter().next().unwrap().name(), "XYZ-Wing");
+    }
+
+    #[test]
+    pub fn test_wxyz_wing_bivalue_pivot() {
+        let s = r#".-----------------.---------------------.-----------------.
+| 1379   139  47  | 6      1457     2   | 349    3459  8  |
+| 2367   5    247 | 9      47       8   | 2346   1     23 |
+| 8      169  24  | 145    145      3   | 2469   4569  7  |
+:-----------------+---------------------+-----------------:
+| 4      8    5   | 12     139      19  | 7      39    6  |
+| 13     2    6   | 14578  1345789  159 | 13489  3459  35 |
+| 13     7    9   | 1458   6        15  | 1348   2     35 |
+:-----------------+---------------------+-----------------:
+| 256    4    28  | 158    158      7   | 236    36    9  |
+| 679    69   1   | 3      2        69  | 5      8     4  |
+| 25679  69   3   | 58     589      4   | 26     67    1  |
+'-----------------'---------------------'-----------------'"#;
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let solution = *BitboardSolver::new().solve(&grid).values();
+        let finder = BentSetFinder::new(4);
+        let mut acc = AllStepAccumulator::default();
+        finder.find_step(&grid, &mut acc);
+        for step in acc.get_steps().iter() {
+            assert_eq!(step.name(), "WXYZ-Wing");
+            let Step::BentSet(bent_set) = step else {
+                panic!("not bent set step");
+            };
+            for cand in bent_set.remove_candidates.iter() {
+                assert_ne!(solution[cand.cell() as usize], cand.value());
+            }
+        }
+        // pivot r9c2 (69) with pincers r8c2, r9c7 and r9c8 removes 6 from r9c1
+        assert!(acc.get_steps().iter().any(|step| match step {
+            Step::BentSet(bent_set) =>
+                bent_set.pivot == vec![Candidate::new(73, 6), Candidate::new(73, 9)]
+                    && bent_set.remove_candidates == vec![Candidate::new(72, 6)],
+            _ => false,
+        }));
+    }
+
+    #[test]
+    pub fn test_vwxyz_wing() {
+        let s = r#".-------------------.----------------.--------------------.
+| 4      1    7     | 3    6     9   | 8      2     5     |
+| 2689   3    25689 | 1    245   458 | 679    49    4679  |
+| 2689   589  25689 | 7    245   458 | 1369   1349  13469 |
+:-------------------+----------------+--------------------:
+| 3789   2    1589  | 4    3579  57  | 13579  6     13789 |
+| 3679   59   1569  | 59   8     26  | 4      1359  12379 |
+| 36789  4    5689  | 59   1     26  | 23579  3589  23789 |
+:-------------------+----------------+--------------------:
+| 289    89   289   | 6    45    3   | 15     7     14    |
+| 5      67   3     | 2    479   1   | 69     489   4689  |
+| 1      67   4     | 589  579   578 | 23569  3589  23689 |
+'-------------------'----------------'--------------------'"#;
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let solution = *BitboardSolver::new().solve(&grid).values();
+        let finder = BentSetFinder::new(5);
+        assert_eq!(finder.name(), "VWXYZ-Wing");
+        let mut acc = AllStepAccumulator::default();
+        finder.find_step(&grid, &mut acc);
+        assert_eq!(acc.get_steps().len(), 3);
+        for step in acc.get_steps().iter() {
+            assert_eq!(step.name(), "VWXYZ-Wing");
+            let Step::BentSet(bent_set) = step else {
+                panic!("not bent set step");
+            };
+            for cand in bent_set.remove_candidates.iter() {
+                assert_ne!(solution[cand.cell() as usize], cand.value());
+            }
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use rand::Rng;
+
+use crate::{
+    grid::Grid,
+    solution::{Solution, SolutionState},
+};
+
+const ALL_DIGITS: u16 = 0x1ff;
+
+/**
+backtracking solver on bit masks, the used digits of every row, column and block are kept in a
+u16 and the empty cell with fewest candidates is tried first. nothing is cloned while searching,
+so it is much faster than the BruteForceSolver and can count or enumerate all the solutions
+**/
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BitboardSolver {}
+
+#[derive(Debug, Clone)]
+struct Board {
+    values: [u8; 81],
+    rows: [u16; 9],
+    cols: [u16; 9],
+    blocks: [u16; 9],
+    // the candidates of the grid, the eliminated candidates are never tried
+    allowed: [u16; 81],
+}
+
+// the cells of the rows, columns and blocks
+const HOUSE_CELLS: [[usize; 9]; 27] = house_cells();
+
+const fn house_cells() -> [[usize; 9]; 27] {
+    let mut cells = [[0; 9]; 27];
+    let mut i = 0;
+    while i < 9 {
+        let mut j = 0;
+        while j < 9 {
+            cells[i][j] = i * 9 + j;
+            cells[9 + i][j] = j * 9 + i;
+            cells[18 + i][j] = (i / 3 * 3 + j / 3) * 9 + i % 3 * 3 + j % 3;
+            j += 1;
+        }
+        i += 1;
+    }
+    cells
+}
+
+fn houses(cell: usize) -> (usize, usize, usize) {
+    let row = cell / 9;
+    let col = cell % 9;
+    (row, col, row / 3 * 3 + col / 3)
+}
+
+impl Board {
+    // None when the values of the grid conflict
+    fn new(grid: &Grid) -> Option<Board> {
+        let mut board = Board {
+            values: [0; 81],
+            rows: [0; 9],
+            cols: [0; 9],
+            blocks: [0; 9],
+            allowed: [ALL_DIGITS; 81],
+        };
+        for cell in 0..81 {
+            let value = grid.get_value(cell as u8);
+            if value == 0 {
+                board.allowed[cell] = grid
+                    .get_cell_candidate(cell as u8)
+                    .iter()
+                    .fold(0, |mask, v| mask | 1 << (v - 1));
+                continue;
+            }
+            if board.candidates(cell) & 1 << (value - 1) == 0 {
+                return None;
+            }
+            board.place(cell, value);
+        }
+        Some(board)
+    }
+
+    fn candidates(&self, cell: usize) -> u16 {
+        let (row, col, block) = houses(cell);
+        self.allowed[cell] & !(self.rows[row] | self.cols[col] | self.blocks[block])
+    }
+
+    fn place(&mut self, cell: usize, value: u8) {
+        let (row, col, block) = houses(cell);
+        let bit = 1 << (value - 1);
+        self.rows[row] |= bit;
+        self.cols[col] |= bit;
+        self.blocks[block] |= bit;
+        self.values[cell] = value;
+    }
+
+    fn unplace(&mut self, cell: usize) {
+        let (row, col, block) = houses(cell);
+        let bit = !(1 << (self.values[cell] - 1));
+        self.rows[row] &= bit;
+        self.cols[col] &= bit;
+        self.blocks[block] &= bit;
+        self.values[cell] = 0;
+    }
+
+    // the empty cell with fewest candidates, None when the board is solved. a hidden single is
+    // returned as a cell with one candidate
+    fn next_cell(&self) -> Option<(usize, u16)> {
+        let mut best: Option<(usize, u16)> = None;
+        let mut candidates = [0; 81];
+        for (cell, value) in self.values.iter().enumerate() {
+            if *value != 0 {
+                continue;
+            }
+            candidates[cell] = self.candidates(cell);
+            let count = candidates[cell].count_ones();
+            if best.is_none_or(|(_, c)| count < c.count_ones()) {
+                best = Some((cell, candidates[cell]));
+                if count <= 1 {
+                    return best;
+                }
+            }
+        }
+        best?;
+        for (house, cells) in HOUSE_CELLS.iter().enumerate() {
+            let mut once = 0;
+            let mut more = 0;
+            for cell in cells {
+                more |= once & candidates[*cell];
+                once |= candidates[*cell];
+            }
+            let used = match house {
+                0..9 => self.rows[house],
+                9..18 => self.cols[house - 9],
+                _ => self.blocks[house - 18],
+            };
+            // a digit can't be placed in the house, one of its empty cells is returned without
+            // candidate
+            if (once | used) != ALL_DIGITS {
+                let cell = cells.iter().find(|c| self.values[**c] == 0).unwrap();
+                return Some((*cell, 0));
+            }
+            let singles = once & !more;
+            if singles != 0 {
+                let bit = singles & singles.wrapping_neg();
+                let cell = cells.iter().find(|c| candidates[**c] & bit != 0).unwrap();
+                return Some((*cell, bit));
+            }
+        }
+        best
+    }
+}
+
+struct Frame {
+    cell: usize,
+    remaining: u16,
+}
+
+// the solutions are found one by one when iterating
+pub struct Solutions {
+    board: Option<Board>,
+    stack: Vec<Frame>,
+    started: bool,
+}
+
+impl Solutions {
+    // try the next candidate of the deepest cell, false when all tried
+    fn backtrack(&mut self) -> bool {
+        let board = self.board.as_mut().unwrap();
+        while let Some(frame) = self.stack.last_mut() {
+            if board.values[frame.cell] != 0 {
+                board.unplace(frame.cell);
+            }
+            if frame.remaining == 0 {
+                self.stack.pop();
+                continue;
+            }
+            let bit = frame.remaining & frame.remaining.wrapping_neg();
+            frame.remaining ^= bit;
+            board.place(frame.cell, bit.trailing_zeros() as u8 + 1);
+            return true;
+        }
+        false
+    }
+}
+
+impl Iterator for Solutions {
+    type Item = [u8; 81];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.board.as_ref()?;
+        if self.started && !self.backtrack() {
+            self.board = None;
+            return None;
+        }
+        self.started = true;
+        loop {
+            let board = self.board.as_ref().unwrap();
+            match board.next_cell() {
+                None => return Some(board.values),
+                Some((cell, remaining)) => {
+                    // an empty cell without candidate is pushed too, backtrack pops it
+                    self.stack.push(Frame { cell, remaining });
+                    if !self.backtrack() {
+                        self.board = None;
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl BitboardSolver {
+    pub fn new() -> Self {
+        BitboardSolver {}
+    }
+
+    pub fn solutions(&self, grid: &Grid) -> Solutions {
+        Solutions {
+            board: Board::new(grid),
+            stack: Vec::with_capacity(81),
+            started: false,
+        }
+    }
+
+    // the count stops at limit
+    pub fn count_solutions(&self, grid: &Grid, limit: usize) -> usize {
+        self.solutions(grid).take(limit).count()
+    }
+
+    pub fn get_solution_state(&self, grid: &Grid) -> SolutionState {
+        match self.count_solutions(grid, 2) {
+            0 => SolutionState::NoSolution,
+            1 => SolutionState::Unique,
+            _ => SolutionState::MoreThanOne,
+        }
+    }
+
+    pub fn solve(&self, grid: &Grid) -> Solution {
+        let mut solutions = self.solutions(grid);
+        let Some(first) = solutions.next() else {
+            return Solution::new([0; 81], SolutionState::NoSolution);
+        };
+        match solutions.next() {
+            Some(other) => Solution::new(first, SolutionState::MoreThanOne).with_other(other),
+            None => Solution::new(first, SolutionState::Unique),
+        }
+    }
+
+    // a random full grid, the digits of every cell are tried in random order
+    pub fn generate_solution_with_rng<R: Rng>(&self, rng: &mut R) -> Grid {
+        let mut board = Board::new(&Grid::default()).unwrap();
+        Self::fill_random(&mut board, rng);
+        let mut grid = Grid::default();
+        for (cell, value) in board.values.iter().enumerate() {
+            grid.set_value(cell as u8, *value, false);
+        }
+        grid
+    }
+
+    fn fill_random<R: Rng>(board: &mut Board, rng: &mut R) -> bool {
+        let Some((cell, mut remaining)) = board.next_cell() else {
+            return true;
+        };
+        while remaining != 0 {
+            let nth = rng.random_range(0..remaining.count_ones());
+            let bit = (0..9)
+                .map(|i| 1 << i)
+                .filter(|bit| remaining & bit != 0)
+                .nth(nth as usize)
+                .unwrap();
+            remaining ^= bit;
+            board.place(cell, bit.trailing_zeros() as u8 + 1);
+            if Self::fill_random(board, rng) {
+                return true;
+            }
+            board.unplace(cell);
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::{
+        candidate::Candidate,
+        grid::Grid,
+        solution::SolutionState,
+        solver::{bitboard::BitboardSolver, brute_force::BruteForceSolver},
+    };
+
+    #[test]
+    fn test_bitboard_solver() {
+        let solver = BitboardSolver::new();
+        let grid = Grid::new_from_singline_digit(
+            "040000200070205849285409300031000920000070000052000470007908632328501090004000010",
+        )
+        .unwrap();
+        let solution = solver.solve(&grid);
+        assert_eq!(solution.state(), &SolutionState::Unique);
+        assert_eq!(
+            solution.values(),
+            BruteForceSolver::new().solve(&grid).values()
+        );
+        assert_eq!(solver.count_solutions(&grid, 10), 1);
+
+        // the empty grid has many solutions, every one is distinct and valid
+        let empty = Grid::default();
+        let solutions: Vec<[u8; 81]> = solver.solutions(&empty).take(50).collect();
+        assert_eq!(solutions.len(), 50);
+        for (i, values) in solutions.iter().enumerate() {
+            assert!(empty.check_grid_valid(values));
+            assert!(!solutions[..i].contains(values));
+        }
+        assert_eq!(solver.count_solutions(&empty, 1000), 1000);
+        assert_eq!(
+            solver.get_solution_state(&empty),
+            SolutionState::MoreThanOne
+        );
+
+        // no candidate is left in r1c3
+        let mut values = ['.'; 81];
+        values[0] = '1';
+        values[1] = '2';
+        let mut grid = Grid::new_from_singline_digit(&values.iter().collect::<String>()).unwrap();
+        grid.remvoe_candidate(&Candidate::new(2, 3));
+        for v in 4..=9 {
+            grid.remvoe_candidate(&Candidate::new(2, v));
+        }
+        assert_eq!(solver.count_solutions(&grid, 2), 0);
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let generated = solver.generate_solution_with_rng(&mut rng);
+        assert!(generated.is_solved());
+        let again = solver.generate_solution_with_rng(&mut StdRng::seed_from_u64(1));
+        assert_eq!(generated.values(), again.values());
+    }
+}
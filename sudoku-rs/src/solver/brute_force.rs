@@ -1,4 +1,5 @@
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 use crate::{
     grid::Grid,
@@ -24,13 +25,12 @@ impl BruteForceSolver {
         BruteForceSolver {}
     }
 
-    fn generate_cell_order(&self) -> [u8; 81] {
+    fn generate_cell_order<R: Rng>(&self, rng: &mut R) -> [u8; 81] {
         let mut cells = [0; 81];
         for i in 0..81 {
             cells[i] = i as u8;
         }
         for _ in 0..81 {
-            let mut rng = rand::rng();
             let a: usize = rng.random_range(0..81);
             let b: usize = rng.random_range(0..81);
             cells.swap(a, b);
@@ -39,7 +39,16 @@ impl BruteForceSolver {
     }
 
     pub fn generate_solution(&self) -> Option<Grid> {
-        let cells = self.generate_cell_order();
+        self.generate_solution_with_rng(&mut rand::rng())
+    }
+
+    pub fn generate_solution_with_seed(&self, seed: u64) -> Option<Grid> {
+        self.generate_solution_with_rng(&mut ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    // the same rng state generates the same solution
+    pub fn generate_solution_with_rng<R: Rng>(&self, rng: &mut R) -> Option<Grid> {
+        let cells = self.generate_cell_order(rng);
         let mut level = 0;
         let mut tries = 0;
         let mut stack = vec![SolverState::default(); 81];
@@ -114,6 +123,7 @@ impl BruteForceSolver {
         let mut solution_count: u8 = 0;
         stack[0].grid = grid.to_owned();
         let mut solutions: [u8; 81] = [0; 81];
+        let mut other = None;
         let max_solution_count = 2;
 
         if stack[0].grid.is_solved() {
@@ -132,6 +142,8 @@ impl BruteForceSolver {
                 solution_count += 1;
                 if solution_count == 1 {
                     solutions = stack[level].grid.values().to_owned();
+                } else if solution_count == 2 {
+                    other = Some(stack[level].grid.values().to_owned());
                 }
                 if solution_count > max_solution_count {
                     break;
@@ -180,7 +192,10 @@ impl BruteForceSolver {
             1 => SolutionState::Unique,
             _ => SolutionState::MoreThanOne,
         };
-        Solution::new(solutions, state)
+        match other {
+            Some(other) => Solution::new(solutions, state).with_other(other),
+            None => Solution::new(solutions, state),
+        }
     }
     fn fill_singles(&self, grid: &mut Grid) {
         let finders: Vec<Box<dyn SolverStrategy>> = vec![
@@ -216,8 +231,10 @@ mod test {
     #[test]
     fn test_solution_generation() {
         let brute_force = BruteForceSolver::new();
-        let solution = brute_force.generate_solution().unwrap();
-        println!("{:?}", solution.to_digit_line())
+        let solution = brute_force.generate_solution_with_seed(1).unwrap();
+        assert!(solution.is_solved());
+        let again = brute_force.generate_solution_with_seed(1).unwrap();
+        assert_eq!(solution.values(), again.values());
     }
     #[test]
     fn test_solution_state() {
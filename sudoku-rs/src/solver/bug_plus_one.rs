@@ -6,6 +6,7 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BugPlusOne {
     pub remove_candidates: Vec<Candidate>,
 }
@@ -1,10 +1,11 @@
 use std::collections::VecDeque;
 
 use crate::candidate::Candidate;
-use crate::grid_constant::{get_cell_buddies, get_cell_house};
+use crate::grid_constant::get_common_buddies;
 use crate::solver::SolverStrategy;
 use crate::solver::chain::link::{Inference, InferenceType};
 use crate::solver::chain::{ChainStep, ChainType};
+use crate::util::indexset::IndexSet;
 use crate::{
     grid::Grid,
     solver::{
@@ -46,14 +47,15 @@ impl AicType1Finder {
             let current_chain = queue.pop_front().unwrap();
             let last = current_chain.inferences.last().unwrap();
             let last_node = last.end.to_owned();
-            let used_cell: Vec<u8> = current_chain
+            let used_cell = current_chain
                 .inferences
                 .iter()
-                .map(|infer| infer.start.cell())
-                .collect();
+                .fold(IndexSet::new_empty(), |u, infer| {
+                    u.union(&infer.start.cells())
+                });
 
             for l in graph.edges[&last_node].iter() {
-                if used_cell.contains(&l.end.cell()) {
+                if !used_cell.intersect(&l.end.cells()).is_empty() {
                     continue;
                 }
                 let mut chain = current_chain.clone();
@@ -88,10 +90,8 @@ impl AicType1Finder {
                         && last.inference_type == InferenceType::Strong
                     {
                         if first.start.value() == last.end.value() {
-                            let start_cell = first.start.cell();
-                            let last_cell = last.end.cell();
-                            let common_bddues = get_cell_buddies(start_cell)
-                                .intersect(&get_cell_buddies(last_cell));
+                            let common_bddues =
+                                get_common_buddies(&first.start.cells().union(&last.end.cells()));
                             let remove_cells: Vec<u8> = common_bddues
                                 .iter()
                                 .filter(|c| grid.cell_has_candidate(*c, first.start.value()))
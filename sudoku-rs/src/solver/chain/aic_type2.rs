@@ -1,9 +1,11 @@
 use std::collections::VecDeque;
 
 use crate::candidate::Candidate;
+use crate::grid_constant::get_common_buddies;
 use crate::solver::SolverStrategy;
 use crate::solver::chain::link::{Inference, InferenceType};
 use crate::solver::chain::{ChainStep, ChainType};
+use crate::util::indexset::IndexSet;
 use crate::{
     grid::Grid,
     solver::{
@@ -45,14 +47,15 @@ impl AicType2Finder {
             let current_chain = queue.pop_front().unwrap();
             let last = current_chain.inferences.last().unwrap();
             let last_node = last.end.to_owned();
-            let used_cell: Vec<u8> = current_chain
+            let used_cell = current_chain
                 .inferences
                 .iter()
-                .map(|infer| infer.start.cell())
-                .collect();
+                .fold(IndexSet::new_empty(), |u, infer| {
+                    u.union(&infer.start.cells())
+                });
 
             for l in graph.edges[&last_node].iter() {
-                if used_cell.contains(&l.end.cell()) {
+                if !used_cell.intersect(&l.end.cells()).is_empty() {
                     continue;
                 }
                 let mut chain = current_chain.clone();
@@ -88,15 +91,27 @@ impl AicType2Finder {
                     {
                         let start_value = first.start.value();
                         let end_value = last.end.value();
-                        let start_cell = first.start.cell();
-                        let last_cell = last.end.cell();
+                        let start_cells = first.start.cells();
+                        let last_cells = last.end.cells();
                         if start_value != end_value {
+                            // a cell of one end can only be removed if it sees every cell of
+                            // the other end
                             let mut remove_candidates: Vec<Candidate> = Vec::new();
-                            if grid.get_cell_candidate(start_cell).contains(end_value) {
-                                remove_candidates.push(Candidate::new(start_cell, end_value));
+                            if !first.start.is_group()
+                                && get_common_buddies(&last_cells).intersect(&start_cells)
+                                    == start_cells
+                                && grid.cell_has_candidate(first.start.cell(), end_value)
+                            {
+                                remove_candidates
+                                    .push(Candidate::new(first.start.cell(), end_value));
                             }
-                            if grid.get_cell_candidate(last_cell).contains(start_value) {
-                                remove_candidates.push(Candidate::new(last_cell, start_value));
+                            if !last.end.is_group()
+                                && get_common_buddies(&start_cells).intersect(&last_cells)
+                                    == last_cells
+                                && grid.cell_has_candidate(last.end.cell(), start_value)
+                            {
+                                remove_candidates
+                                    .push(Candidate::new(last.end.cell(), start_value));
                             }
                             if remove_candidates.is_empty() {
                                 continue;
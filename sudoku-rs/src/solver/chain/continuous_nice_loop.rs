@@ -3,7 +3,7 @@ use std::collections::VecDeque;
 use crate::{
     candidate::Candidate,
     grid::Grid,
-    grid_constant::get_cell_buddies,
+    grid_constant::get_common_buddies,
     solver::{
         SolverStrategy,
         chain::{
@@ -14,6 +14,7 @@ use crate::{
         step::Step,
         step_accumulator::StepAccumulator,
     },
+    util::indexset::IndexSet,
 };
 
 #[derive(Default)]
@@ -49,28 +50,40 @@ impl ContinuousNiceLoopFinder {
             }
             while !queue.is_empty() {
                 let current_chain = queue.pop_front().unwrap();
-                let used_cells: Vec<u8> = current_chain
+                let used_cells = current_chain
                     .inferences
                     .iter()
-                    .map(|inference| inference.end.cell())
-                    .collect();
+                    .fold(IndexSet::new_empty(), |u, inference| {
+                        u.union(&inference.end.cells())
+                    });
                 let last = current_chain.last().unwrap().to_owned();
-                for edge in graph.edges[&last.end.cell()].iter() {
-                    if used_cells.contains(&edge.end.cell()) {
+                for edge in graph.node_edges(&last.end).iter() {
+                    if !used_cells.intersect(&edge.end.cells()).is_empty() {
                         continue;
                     }
+                    // a group node can not contain the start cell of the loop
+                    if edge.end.is_group() && edge.end.cells().contains(start_cell) {
+                        continue;
+                    }
+                    // group node only links to the same value, a single node can switch value
+                    // inside the cell
+                    let start = if last.end.value() == edge.end.value() {
+                        last.end
+                    } else {
+                        Candidate::new(last.end.cell(), edge.end.value()).into()
+                    };
                     let mut new_chain = current_chain.clone();
                     match (&last.inference_type, &edge.link_type) {
                         (InferenceType::Strong, LinkType::Strong) => {
                             if last.end.value() == edge.end.value() {
                                 new_chain.add_inference(Inference::new(
-                                    Candidate::new(last.end.cell(), edge.end.value()),
+                                    start,
                                     edge.end.clone(),
                                     InferenceType::Weak,
                                 ));
                             } else {
                                 new_chain.add_inference(Inference::new(
-                                    Candidate::new(last.end.cell(), edge.end.value()),
+                                    start,
                                     edge.end.clone(),
                                     InferenceType::Strong,
                                 ));
@@ -79,7 +92,7 @@ impl ContinuousNiceLoopFinder {
                         (InferenceType::Strong, LinkType::Weak) => {
                             if last.end.value() == edge.end.value() {
                                 new_chain.add_inference(Inference::new(
-                                    Candidate::new(last.end.cell(), edge.end.value()),
+                                    start,
                                     edge.end.clone(),
                                     InferenceType::Weak,
                                 ));
@@ -88,7 +101,7 @@ impl ContinuousNiceLoopFinder {
                         (InferenceType::Weak, LinkType::Strong) => {
                             if last.end.value() == edge.end.value() {
                                 new_chain.add_inference(Inference::new(
-                                    Candidate::new(last.end.cell(), edge.end.value()),
+                                    start,
                                     edge.end.clone(),
                                     InferenceType::Strong,
                                 ));
@@ -99,7 +112,7 @@ impl ContinuousNiceLoopFinder {
                                 && grid.get_cell_candidate(last.end.cell()).count() == 2
                             {
                                 new_chain.add_inference(Inference::new(
-                                    Candidate::new(last.end.cell(), edge.end.value()),
+                                    start,
                                     edge.end.clone(),
                                     InferenceType::Weak,
                                 ));
@@ -111,11 +124,11 @@ impl ContinuousNiceLoopFinder {
                     }
                     let first = new_chain.inferences.first().unwrap().to_owned();
                     if new_chain.len() >= 4 {
-                        if first.start.cell() == edge.end.cell() {
+                        if first.start.cells() == edge.end.cells() {
                             self.check_continuous_niceloop(grid, acc, new_chain.clone());
                         }
                     }
-                    if first.start.cell() == edge.end.cell() {
+                    if first.start.cells() == edge.end.cells() {
                         continue;
                     }
 
@@ -169,8 +182,7 @@ impl ContinuousNiceLoopFinder {
                     let start = &inference.start;
                     let end = &inference.end;
                     let value = start.value();
-                    let common_buddies =
-                        get_cell_buddies(start.cell()).intersect(&get_cell_buddies(end.cell()));
+                    let common_buddies = get_common_buddies(&start.cells().union(&end.cells()));
                     let remove_cells: Vec<u8> = common_buddies
                         .iter()
                         .filter(|c| grid.cell_has_candidate(*c, value))
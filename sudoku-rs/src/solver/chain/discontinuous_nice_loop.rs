@@ -13,6 +13,7 @@ use crate::{
         step::Step,
         step_accumulator::StepAccumulator,
     },
+    util::indexset::IndexSet,
 };
 
 #[derive(Default)]
@@ -48,22 +49,34 @@ impl DiscontinuousNiceLoopFinder {
             }
             while !queue.is_empty() {
                 let current_chain = queue.pop_front().unwrap();
-                let used_cells: Vec<u8> = current_chain
+                let used_cells = current_chain
                     .inferences
                     .iter()
-                    .map(|inference| inference.end.cell())
-                    .collect();
+                    .fold(IndexSet::new_empty(), |u, inference| {
+                        u.union(&inference.end.cells())
+                    });
                 let last = current_chain.last().unwrap().to_owned();
-                for edge in graph.edges[&last.end.cell()].iter() {
-                    if used_cells.contains(&edge.end.cell()) {
+                for edge in graph.node_edges(&last.end).iter() {
+                    if !used_cells.intersect(&edge.end.cells()).is_empty() {
                         continue;
                     }
+                    // a group node can not contain the start cell of the loop
+                    if edge.end.is_group() && edge.end.cells().contains(start_cell) {
+                        continue;
+                    }
+                    // group node only links to the same value, a single node can switch value
+                    // inside the cell
+                    let start = if last.end.value() == edge.end.value() {
+                        last.end
+                    } else {
+                        Candidate::new(last.end.cell(), edge.end.value()).into()
+                    };
                     let mut new_chain = current_chain.clone();
                     match (&last.inference_type, &edge.link_type) {
                         (InferenceType::Strong, LinkType::Strong) => {
                             if last.end.value() != edge.end.value() {
                                 new_chain.add_inference(Inference::new(
-                                    Candidate::new(last.end.cell(), edge.end.value()),
+                                    start,
                                     edge.end.clone(),
                                     InferenceType::Strong,
                                 ));
@@ -72,7 +85,7 @@ impl DiscontinuousNiceLoopFinder {
                         (InferenceType::Strong, LinkType::Weak) => {
                             if last.end.value() == edge.end.value() {
                                 new_chain.add_inference(Inference::new(
-                                    Candidate::new(last.end.cell(), edge.end.value()),
+                                    start,
                                     edge.end.clone(),
                                     InferenceType::Weak,
                                 ));
@@ -81,7 +94,7 @@ impl DiscontinuousNiceLoopFinder {
                         (InferenceType::Weak, LinkType::Strong) => {
                             if last.end.value() == edge.end.value() {
                                 new_chain.add_inference(Inference::new(
-                                    Candidate::new(last.end.cell(), edge.end.value()),
+                                    start,
                                     edge.end.clone(),
                                     InferenceType::Strong,
                                 ));
@@ -92,7 +105,7 @@ impl DiscontinuousNiceLoopFinder {
                                 && grid.get_cell_candidate(last.end.cell()).count() == 2
                             {
                                 new_chain.add_inference(Inference::new(
-                                    Candidate::new(last.end.cell(), edge.end.value()),
+                                    start,
                                     edge.end.clone(),
                                     InferenceType::Weak,
                                 ));
@@ -104,11 +117,11 @@ impl DiscontinuousNiceLoopFinder {
                     }
                     let first = new_chain.inferences.first().unwrap().to_owned();
                     if new_chain.len() >= 4 {
-                        if first.start.cell() == edge.end.cell() {
+                        if first.start.cells() == edge.end.cells() {
                             self.check_discontinuous_niceloop(grid, acc, new_chain.clone());
                         }
                     }
-                    if first.start.cell() == edge.end.cell() {
+                    if first.start.cells() == edge.end.cells() {
                         continue;
                     }
                     // TODO make max length of chain configable
@@ -0,0 +1,467 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    grid_constant::{get_cell_house, get_house_cell_set},
+    solver::{
+        SolverStrategy,
+        chain::{
+            graph::Graph,
+            link::{Chain, Inference, InferenceType, LinkType, Node},
+        },
+        step::Step,
+        step_accumulator::StepAccumulator,
+    },
+    util::format_step::{format_candidates_cells, format_candidates_values},
+};
+
+/**
+forcing chain assume a candidate is true or false, and follow every inference from it.
+a forcing net also uses singles (the last candidate of a cell or a house) during the propagation.
+contradiction: the assumption leads to a candidate being true and false at the same time,
+verity: every candidate of a cell or every position of a digit in a house leads to the same result.
+**/
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ForcingChainType {
+    Contradiction,
+    Verity,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForcingChainStep {
+    pub forcing_type: ForcingChainType,
+    pub net: bool,
+    pub chains: Vec<Chain>,
+    pub set_values: Vec<Candidate>,
+    pub remove_candidates: Vec<Candidate>,
+}
+
+impl ForcingChainStep {
+    pub fn apply(&self, grid: &mut Grid) {
+        for cand in self.set_values.iter() {
+            grid.set_value_with_candidate(cand);
+        }
+        for cand in self.remove_candidates.iter() {
+            grid.remvoe_candidate(cand);
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match (self.net, &self.forcing_type) {
+            (false, ForcingChainType::Contradiction) => "Forcing Chain Contradiction",
+            (false, ForcingChainType::Verity) => "Forcing Chain Verity",
+            (true, ForcingChainType::Contradiction) => "Forcing Net Contradiction",
+            (true, ForcingChainType::Verity) => "Forcing Net Verity",
+        }
+    }
+
+    pub fn difficulty(&self) -> u32 {
+        if self.net { 700 } else { 500 }
+    }
+
+    pub fn explain(&self) -> String {
+        let mut premises: Vec<Candidate> = self
+            .chains
+            .iter()
+            .filter_map(|chain| match chain.inferences.first()?.start {
+                Node::Single(cand) => Some(cand),
+                Node::Group(_, _) => None,
+            })
+            .collect();
+        premises.dedup();
+        let result = if self.set_values.is_empty() {
+            format!(
+                "{} can be removed from {}",
+                format_candidates_values(&self.remove_candidates),
+                format_candidates_cells(&self.remove_candidates)
+            )
+        } else {
+            format!(
+                "{} can be set to {}",
+                format_candidates_cells(&self.set_values),
+                format_candidates_values(&self.set_values)
+            )
+        };
+        match self.forcing_type {
+            ForcingChainType::Contradiction => format!(
+                "<h3>{}</h3><p>assume {} is {}, it leads to a candidate being true and false at the same time, so {}</p>",
+                self.name(),
+                format_candidates_cells(&premises),
+                format_candidates_values(&premises),
+                result
+            ),
+            ForcingChainType::Verity => format!(
+                "<h3>{}</h3><p>one of {} must be true, all of them lead to the same result, so {}</p>",
+                self.name(),
+                format_candidates_cells(&premises),
+                result
+            ),
+        }
+    }
+}
+
+// the result of assuming one candidate true or false
+pub(crate) struct Propagation {
+    // candidate -> inference which made it true (none for the premise)
+    pub(crate) on: BTreeMap<Candidate, Option<Inference>>,
+    pub(crate) off: BTreeMap<Candidate, Option<Inference>>,
+    pub(crate) contradiction: Option<Candidate>,
+}
+
+impl Propagation {
+    fn state(&self, on: bool) -> &BTreeMap<Candidate, Option<Inference>> {
+        if on { &self.on } else { &self.off }
+    }
+
+    // walk back from the candidate to the premise
+    pub(crate) fn chain_to(&self, cand: Candidate, on: bool) -> Chain {
+        let mut inferences = Vec::new();
+        let mut current = cand;
+        let mut current_on = on;
+        while let Some(Some(inference)) = self.state(current_on).get(&current) {
+            inferences.push(inference.clone());
+            let Node::Single(start) = inference.start else {
+                break;
+            };
+            current = start;
+            // weak inference: start on -> end off, strong inference: start off -> end on
+            current_on = inference.inference_type == InferenceType::Weak;
+            if inferences.len() > 81 * 9 {
+                break;
+            }
+        }
+        inferences.reverse();
+        Chain { inferences }
+    }
+}
+
+pub struct ForcingChainFinder {
+    net: bool,
+}
+
+impl ForcingChainFinder {
+    pub fn new(net: bool) -> Self {
+        ForcingChainFinder { net }
+    }
+
+    pub(crate) fn propagate(
+        &self,
+        grid: &Grid,
+        graph: &Graph,
+        premise: Candidate,
+        on: bool,
+    ) -> Propagation {
+        let mut propagation = Propagation {
+            on: BTreeMap::new(),
+            off: BTreeMap::new(),
+            contradiction: None,
+        };
+        let mut queue = VecDeque::new();
+        if on {
+            propagation.on.insert(premise, None);
+        } else {
+            propagation.off.insert(premise, None);
+        }
+        queue.push_back((premise, on));
+        while let Some((cand, cand_on)) = queue.pop_front() {
+            // a net uses the singles of the current state instead of the strong links
+            let (derived, inference_type) = if cand_on {
+                (self.linked(graph, cand, true), InferenceType::Weak)
+            } else if self.net {
+                (
+                    self.find_singles(grid, &propagation, cand),
+                    InferenceType::Strong,
+                )
+            } else {
+                (self.linked(graph, cand, false), InferenceType::Strong)
+            };
+            let end_on = !cand_on;
+            for end in derived {
+                let (same, opposite) = if end_on {
+                    (&mut propagation.on, &propagation.off)
+                } else {
+                    (&mut propagation.off, &propagation.on)
+                };
+                if same.contains_key(&end) {
+                    continue;
+                }
+                same.insert(end, Some(Inference::new(cand, end, inference_type.clone())));
+                if opposite.contains_key(&end) {
+                    propagation.contradiction = Some(end);
+                    return propagation;
+                }
+                queue.push_back((end, end_on));
+            }
+            if self.net && !cand_on && self.is_broken(grid, &propagation, cand) {
+                // the cell or a house of the last false candidate has nothing left
+                propagation.contradiction = Some(cand);
+                return propagation;
+            }
+        }
+        propagation
+    }
+
+    // a true candidate turns every candidate linked to it false, a false candidate only turns
+    // its strong links true. group nodes are not used by forcing chains
+    fn linked(&self, graph: &Graph, cand: Candidate, on: bool) -> Vec<Candidate> {
+        graph
+            .edges
+            .get(&cand.into())
+            .map(|edges| {
+                edges
+                    .iter()
+                    .filter(|edge| on || edge.link_type == LinkType::Strong)
+                    .filter_map(|edge| match edge.end {
+                        Node::Single(end) => Some(end),
+                        Node::Group(_, _) => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // candidates become the last one of its cell or the last position of a house after cand is
+    // false
+    fn find_singles(
+        &self,
+        grid: &Grid,
+        propagation: &Propagation,
+        cand: Candidate,
+    ) -> Vec<Candidate> {
+        let mut singles = Vec::new();
+        let left: Vec<Candidate> = grid
+            .get_cell_candidate(cand.cell())
+            .iter()
+            .map(|v| Candidate::new(cand.cell(), v))
+            .filter(|c| !propagation.off.contains_key(c))
+            .collect();
+        if left.len() == 1 && !propagation.on.contains_key(&left[0]) {
+            singles.push(left[0]);
+        }
+        for house in get_cell_house(cand.cell()) {
+            let left: Vec<Candidate> = get_house_cell_set(house)
+                .iter()
+                .filter(|c| grid.cell_has_candidate(*c, cand.value()))
+                .map(|c| Candidate::new(c, cand.value()))
+                .filter(|c| !propagation.off.contains_key(c))
+                .collect();
+            if left.len() == 1
+                && !propagation.on.contains_key(&left[0])
+                && !singles.contains(&left[0])
+            {
+                singles.push(left[0]);
+            }
+        }
+        singles
+    }
+
+    fn is_broken(&self, grid: &Grid, propagation: &Propagation, cand: Candidate) -> bool {
+        let cell_empty = grid.get_cell_candidate(cand.cell()).iter().all(|v| {
+            propagation
+                .off
+                .contains_key(&Candidate::new(cand.cell(), v))
+        });
+        if cell_empty {
+            return true;
+        }
+        get_cell_house(cand.cell()).iter().any(|house| {
+            get_house_cell_set(*house)
+                .iter()
+                .filter(|c| grid.cell_has_candidate(*c, cand.value()))
+                .all(|c| {
+                    propagation
+                        .off
+                        .contains_key(&Candidate::new(c, cand.value()))
+                })
+        })
+    }
+
+    fn contradiction_chains(&self, propagation: &Propagation) -> Vec<Chain> {
+        let cand = propagation.contradiction.unwrap();
+        let mut chains = vec![propagation.chain_to(cand, false)];
+        if propagation.on.contains_key(&cand) {
+            chains.push(propagation.chain_to(cand, true));
+        }
+        chains
+    }
+
+    fn find_contradiction(&self, grid: &Grid, graph: &Graph, acc: &mut dyn StepAccumulator) {
+        for cell in 0..81_u8 {
+            for v in grid.get_cell_candidate(cell).iter() {
+                let premise = Candidate::new(cell, v);
+                for on in [true, false] {
+                    let propagation = self.propagate(grid, graph, premise, on);
+                    if propagation.contradiction.is_none() {
+                        continue;
+                    }
+                    let (set_values, remove_candidates) = if on {
+                        (Vec::new(), vec![premise])
+                    } else {
+                        (vec![premise], Vec::new())
+                    };
+                    let step = ForcingChainStep {
+                        forcing_type: ForcingChainType::Contradiction,
+                        net: self.net,
+                        chains: self.contradiction_chains(&propagation),
+                        set_values,
+                        remove_candidates,
+                    };
+                    if acc.add_step(Step::ForcingChain(step)) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_verity(
+        &self,
+        grid: &Grid,
+        graph: &Graph,
+        premises: &[Candidate],
+        acc: &mut dyn StepAccumulator,
+    ) {
+        let propagations: Vec<Propagation> = premises
+            .iter()
+            .map(|premise| self.propagate(grid, graph, *premise, true))
+            .collect();
+        // a premise leads to contradiction is handled by find_contradiction
+        if propagations.iter().any(|p| p.contradiction.is_some()) {
+            return;
+        }
+        let first = &propagations[0];
+        for (state, on) in [(&first.on, true), (&first.off, false)] {
+            for cand in state.keys() {
+                if premises.contains(cand) {
+                    continue;
+                }
+                if !propagations.iter().all(|p| p.state(on).contains_key(cand)) {
+                    continue;
+                }
+                let (set_values, remove_candidates) = if on {
+                    (vec![*cand], Vec::new())
+                } else {
+                    (Vec::new(), vec![*cand])
+                };
+                let chains: Vec<Chain> =
+                    propagations.iter().map(|p| p.chain_to(*cand, on)).collect();
+                let step = ForcingChainStep {
+                    forcing_type: ForcingChainType::Verity,
+                    net: self.net,
+                    chains,
+                    set_values,
+                    remove_candidates,
+                };
+                if acc.add_step(Step::ForcingChain(step)) {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn find_verity(&self, grid: &Grid, graph: &Graph, acc: &mut dyn StepAccumulator) {
+        // cell forcing: one of the candidates of a cell is true
+        for cell in 0..81_u8 {
+            let candidates = grid.get_cell_candidate(cell);
+            if candidates.count() < 2 {
+                continue;
+            }
+            let premises: Vec<Candidate> =
+                candidates.iter().map(|v| Candidate::new(cell, v)).collect();
+            self.check_verity(grid, graph, &premises, acc);
+            if acc.is_finish() {
+                return;
+            }
+        }
+        // digit forcing: one of the positions of a digit in a house is true
+        for house in 0..27 {
+            for v in 1..=9 {
+                let cells = grid.pential_cells_in_house(house, v);
+                if cells.count() < 2 {
+                    continue;
+                }
+                let premises: Vec<Candidate> = cells.iter().map(|c| Candidate::new(c, v)).collect();
+                self.check_verity(grid, graph, &premises, acc);
+                if acc.is_finish() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl SolverStrategy for ForcingChainFinder {
+    fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        let graph = Graph::new_aic_graph(grid);
+        self.find_contradiction(grid, &graph, acc);
+        if acc.is_finish() {
+            return;
+        }
+        self.find_verity(grid, &graph, acc);
+    }
+    fn name(&self) -> &str {
+        if self.net {
+            "ForcingNetFinder"
+        } else {
+            "ForcingChainFinder"
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        grid::Grid,
+        solver::{
+            SolverStrategy, chain::forcing_chain::ForcingChainFinder, step::Step,
+            step_accumulator::AllStepAccumulator,
+        },
+    };
+
+    #[test]
+    fn test_forcing_chain() {
+        let s = r#".-----------------.---------------.------------.
+| 4    8      7   | 3   12    12  | 56  9   56 |
+| 59   359    39  | 6   48    48  | 2   7   1  |
+| 1    2      6   | 57  9     57  | 3   8   4  |
+:-----------------+---------------+------------:
+| 7    349    5   | 89  348   489 | 1   6   2  |
+| 69   13469  349 | 2   1346  57  | 8   34  57 |
+| 28   1346   28  | 57  1346  14  | 57  34  9  |
+:-----------------+---------------+------------:
+| 58   45     1   | 48  7     6   | 9   2   3  |
+| 3    67     89  | 1   28    289 | 4   5   67 |
+| 269  4679   249 | 49  5     3   | 67  1   8  |
+'-----------------'---------------'------------'"#;
+        let solution =
+            "487312695593684271126597384735849162914265837268731549851476923379128456642953718";
+        let solution: Vec<u8> = solution.bytes().map(|b| b - b'0').collect();
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        for net in [false, true] {
+            let solver = ForcingChainFinder::new(net);
+            let mut acc = AllStepAccumulator::default();
+            solver.find_step(&grid, &mut acc);
+            let steps = acc.get_steps();
+            assert!(!steps.is_empty());
+            for step in steps.iter() {
+                let Step::ForcingChain(fc) = step else {
+                    panic!("not a forcing chain: {:?}", step);
+                };
+                assert_eq!(fc.net, net);
+                for cand in fc.remove_candidates.iter() {
+                    assert_ne!(solution[cand.cell() as usize], cand.value());
+                }
+                for cand in fc.set_values.iter() {
+                    assert_eq!(solution[cand.cell() as usize], cand.value());
+                }
+                let mut g = grid.clone();
+                step.apply(&mut g);
+                assert!(g.check_grid_valid(&solution));
+            }
+        }
+    }
+}
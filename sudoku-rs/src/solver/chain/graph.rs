@@ -1,21 +1,75 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use crate::{
     candidate::Candidate,
     grid::Grid,
-    grid_constant::{get_cell_buddies, get_cell_house},
-    solver::chain::link::LinkType,
+    grid_constant::{get_cell_buddies, get_cell_house, get_house_cell_set},
+    solver::chain::link::{LinkType, Node},
 };
 
 #[derive(Debug, PartialEq)]
 pub struct EdgeInfo {
     pub link_type: LinkType,
-    pub end: Candidate,
+    pub end: Node,
 }
 
 #[derive(Debug, Default)]
 pub struct Graph {
-    pub edges: HashMap<Candidate, Vec<EdgeInfo>>,
+    pub edges: BTreeMap<Node, Vec<EdgeInfo>>,
+}
+
+// group nodes of value: candidates in the intersection of a block and a line, at least 2 cells
+pub fn find_group_nodes(grid: &Grid, value: u8) -> Vec<Node> {
+    let mut groups = Vec::new();
+    for block in 18..27 {
+        let block_cells = grid.pential_cells_in_house(block, value);
+        for line in 0..18 {
+            let cells = block_cells.intersect(&get_house_cell_set(line));
+            if cells.count() >= 2 {
+                groups.push(Node::Group(cells, value));
+            }
+        }
+    }
+    groups
+}
+
+// links between group nodes and the other nodes in the same house, a link is strong if the two
+// nodes cover all positions of value in the house
+pub fn find_group_links(grid: &Grid, value: u8) -> Vec<(Node, Node, LinkType)> {
+    let groups = find_group_nodes(grid, value);
+    let mut links = Vec::new();
+    if groups.is_empty() {
+        return links;
+    }
+    for house in 0..27 {
+        let positions = grid.pential_cells_in_house(house, value);
+        let mut nodes: Vec<Node> = positions
+            .iter()
+            .map(|cell| Node::Single(Candidate::new(cell, value)))
+            .collect();
+        nodes.extend(
+            groups
+                .iter()
+                .filter(|group| group.cells().intersect(&positions) == group.cells()),
+        );
+        for start in nodes.iter() {
+            for end in nodes.iter() {
+                if !start.is_group() && !end.is_group() {
+                    continue;
+                }
+                if !start.cells().intersect(&end.cells()).is_empty() {
+                    continue;
+                }
+                let link_type = if start.cells().union(&end.cells()) == positions {
+                    LinkType::Strong
+                } else {
+                    LinkType::Weak
+                };
+                links.push((*start, *end, link_type));
+            }
+        }
+    }
+    links
 }
 
 impl Graph {
@@ -110,6 +164,7 @@ impl Graph {
                 }
             }
         }
+        graph.add_group_links(grid, x);
         graph
     }
     pub fn new_aic_graph(grid: &Grid) -> Self {
@@ -148,45 +203,57 @@ impl Graph {
                 }
             }
             let cell_houses = get_cell_house(cell);
+            // a conjugate pair in any house makes the link strong, even if the two cells share
+            // another house with more positions
             let mut used = Vec::new();
-            for h in cell_houses.iter() {
-                for v in candidates.iter() {
+            for v in candidates.iter() {
+                for h in cell_houses.iter() {
                     let mut pential_cells = grid.pential_cells_in_house(*h, v);
                     pential_cells.remove(cell);
-                    if pential_cells.count() == 1 {
-                        for end in pential_cells.iter() {
-                            let end_node = Candidate::new(end, v);
-                            if used.contains(&end_node) {
-                                continue;
-                            }
-                            graph.add_link(
-                                Candidate::new(cell, v),
-                                end_node.clone(),
-                                LinkType::Strong,
-                            );
-                            used.push(end_node);
+                    if pential_cells.count() != 1 {
+                        continue;
+                    }
+                    for end in pential_cells.iter() {
+                        let end_node = Candidate::new(end, v);
+                        if used.contains(&end_node) {
+                            continue;
                         }
-                    } else {
-                        for end in pential_cells.iter() {
-                            let end_node = Candidate::new(end, v);
-                            if used.contains(&end_node) {
-                                continue;
-                            }
-                            graph.add_link(
-                                Candidate::new(cell, v),
-                                end_node.clone(),
-                                LinkType::Weak,
-                            );
-                            used.push(end_node);
+                        graph.add_link(Candidate::new(cell, v), end_node, LinkType::Strong);
+                        used.push(end_node);
+                    }
+                }
+            }
+            for v in candidates.iter() {
+                for h in cell_houses.iter() {
+                    let mut pential_cells = grid.pential_cells_in_house(*h, v);
+                    pential_cells.remove(cell);
+                    for end in pential_cells.iter() {
+                        let end_node = Candidate::new(end, v);
+                        if used.contains(&end_node) {
+                            continue;
                         }
+                        graph.add_link(Candidate::new(cell, v), end_node, LinkType::Weak);
+                        used.push(end_node);
                     }
                 }
             }
         }
+        for value in 1..=9 {
+            graph.add_group_links(grid, value);
+        }
         graph
     }
-    pub fn add_link(&mut self, start: Candidate, end: Candidate, link_type: LinkType) {
-        let edge_info = EdgeInfo { end, link_type };
+    pub fn add_group_links(&mut self, grid: &Grid, value: u8) {
+        for (start, end, link_type) in find_group_links(grid, value) {
+            self.add_link(start, end, link_type);
+        }
+    }
+    pub fn add_link(&mut self, start: impl Into<Node>, end: impl Into<Node>, link_type: LinkType) {
+        let start = start.into();
+        let edge_info = EdgeInfo {
+            end: end.into(),
+            link_type,
+        };
         if self.edges.contains_key(&start) {
             if self.edges[&start].contains(&edge_info) {
                 return;
@@ -198,7 +265,9 @@ impl Graph {
 
 #[derive(Default)]
 pub struct CellGraph {
-    pub edges: HashMap<u8, Vec<EdgeInfo>>,
+    pub edges: BTreeMap<u8, Vec<EdgeInfo>>,
+    // edges start from group nodes, only used by nice loop
+    pub group_edges: BTreeMap<Node, Vec<EdgeInfo>>,
 }
 
 impl CellGraph {
@@ -244,11 +313,36 @@ impl CellGraph {
                 }
             }
         }
+        for value in 1..=9 {
+            for (start, end, link_type) in find_group_links(grid, value) {
+                match start {
+                    Node::Single(cand) => graph.add_link(cand.cell(), end, link_type),
+                    Node::Group(_, _) => {
+                        let edge_info = EdgeInfo { end, link_type };
+                        let edges = graph.group_edges.entry(start).or_default();
+                        if !edges.contains(&edge_info) {
+                            edges.push(edge_info);
+                        }
+                    }
+                }
+            }
+        }
         graph
     }
 
-    pub fn add_link(&mut self, start: u8, end: Candidate, link_type: LinkType) {
-        let edge_info = EdgeInfo { end, link_type };
+    pub fn node_edges(&self, node: &Node) -> &[EdgeInfo] {
+        let edges = match node {
+            Node::Single(cand) => self.edges.get(&cand.cell()),
+            Node::Group(_, _) => self.group_edges.get(node),
+        };
+        edges.map(|edges| edges.as_slice()).unwrap_or(&[])
+    }
+
+    pub fn add_link(&mut self, start: u8, end: impl Into<Node>, link_type: LinkType) {
+        let edge_info = EdgeInfo {
+            end: end.into(),
+            link_type,
+        };
         if self.edges.contains_key(&start) {
             if self.edges.get(&start).unwrap().contains(&edge_info) {
                 return;
@@ -267,7 +361,9 @@ mod test {
     use crate::grid::Grid;
     use crate::solver::chain::graph::CellGraph;
     use crate::solver::chain::graph::Graph;
-    use crate::solver::chain::link::LinkType;
+    use crate::solver::chain::graph::find_group_nodes;
+    use crate::solver::chain::link::{LinkType, Node};
+    use crate::util::indexset::IndexSet;
 
     #[test]
     fn test_add_link() {
@@ -286,7 +382,10 @@ mod test {
         for (key, edges) in graph.edges.iter() {
             println!("{:?},{:?}", key, edges);
         }
-        assert_eq!(graph.edges.get(&Candidate::new(2, 4)).unwrap().len(), 3);
+        assert_eq!(
+            graph.edges.get(&Candidate::new(2, 4).into()).unwrap().len(),
+            3
+        );
     }
     #[test]
     fn test_nice_graph_create() {
@@ -297,4 +396,61 @@ mod test {
             println!("{:?},{:?}", key, edges);
         }
     }
+    #[test]
+    fn test_conjugate_link() {
+        let s = r#".-------------------.----------------.--------------------.
+| 4      1    7     | 3    6     9   | 8      2     5     |
+| 2689   3    25689 | 1    245   458 | 679    49    4679  |
+| 2689   589  25689 | 7    245   458 | 1369   1349  13469 |
+:-------------------+----------------+--------------------:
+| 3789   2    1589  | 4    3579  57  | 13579  6     13789 |
+| 3679   59   1569  | 59   8     26  | 4      1359  12379 |
+| 36789  4    5689  | 59   1     26  | 23579  3589  23789 |
+:-------------------+----------------+--------------------:
+| 289    89   289   | 6    45    3   | 15     7     14    |
+| 5      67   3     | 2    479   1   | 69     489   4689  |
+| 1      67   4     | 589  579   578 | 23569  3589  23689 |
+'-------------------'----------------'--------------------'"#;
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let graph = Graph::new_aic_graph(&grid);
+        // 7 of r4c5 and r4c6 is a conjugate pair in block 5 but not in row 4
+        let edges = graph.edges.get(&Candidate::new(31, 7).into()).unwrap();
+        let links: Vec<&LinkType> = edges
+            .iter()
+            .filter(|edge| edge.end == Candidate::new(32, 7).into())
+            .map(|edge| &edge.link_type)
+            .collect();
+        assert_eq!(links, vec![&LinkType::Strong]);
+    }
+    #[test]
+    fn test_group_links() {
+        let s = r#".-----------------.-------------.--------------------.
+| 3      79   4   | 5    2  16  | 169    8     1679  |
+| 1278   278  6   | 348  9  138 | 1245   1457  1247  |
+| 1289   5    12  | 48   7  168 | 3      149   12469 |
+:-----------------+-------------+--------------------:
+| 1457   47   157 | 6    8  9   | 145    2     3     |
+| 12589  289  125 | 7    3  4   | 15689  159   1689  |
+| 489    6    3   | 1    5  2   | 7      49    489   |
+:-----------------+-------------+--------------------:
+| 2457   1    257 | 9    6  358 | 248    347   2478  |
+| 257    237  9   | 38   4  358 | 128    6     1278  |
+| 6      34   8   | 2    1  7   | 49     349   5     |
+'-----------------'-------------'--------------------'"#;
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        // r1c2 and r2c2 in block 1 and col 2
+        let group = Node::Group(IndexSet::new_from_values([1, 10].into_iter()), 7);
+        assert!(find_group_nodes(&grid, 7).contains(&group));
+        let graph = Graph::new_x_chain_graph(&grid, 7);
+        let edges = graph.edges.get(&group).unwrap();
+        // 7 in block 1 is r2c1 or the group
+        assert!(
+            edges
+                .iter()
+                .any(|edge| edge.end == Candidate::new(9, 7).into()
+                    && edge.link_type == LinkType::Strong)
+        );
+        let graph = CellGraph::new_nice_loop_graph(&grid);
+        assert_eq!(graph.node_edges(&group).len(), edges.len());
+    }
 }
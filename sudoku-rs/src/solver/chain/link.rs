@@ -1,6 +1,4 @@
-use std::collections::HashSet;
-
-use crate::candidate::Candidate;
+use crate::{candidate::Candidate, util::indexset::IndexSet};
 
 /**
 link used for create  graph
@@ -9,45 +7,107 @@ strong link can be strong inference and weak inference but weak link can only be
 **/
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LinkType {
     Strong,
     Weak,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Link {
     link_type: LinkType,
-    start: Candidate,
-    end: Candidate,
+    start: Node,
+    end: Node,
+}
+
+/**
+node of the chain, a single candidate or a group node: all candidates of one digit in the
+intersection of a block and a line, the group is true if one of its candidates is true
+**/
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Node {
+    Single(Candidate),
+    Group(IndexSet, u8),
+}
+
+impl Node {
+    pub fn value(&self) -> u8 {
+        match self {
+            Node::Single(cand) => cand.value(),
+            Node::Group(_, value) => *value,
+        }
+    }
+
+    // the first cell of a group node
+    pub fn cell(&self) -> u8 {
+        match self {
+            Node::Single(cand) => cand.cell(),
+            Node::Group(cells, _) => cells.iter().next().unwrap(),
+        }
+    }
+
+    pub fn cells(&self) -> IndexSet {
+        match self {
+            Node::Single(cand) => IndexSet::new_from_values([cand.cell()].into_iter()),
+            Node::Group(cells, _) => *cells,
+        }
+    }
+
+    pub fn is_group(&self) -> bool {
+        matches!(self, Node::Group(_, _))
+    }
+
+    pub fn candidates(&self) -> Vec<Candidate> {
+        self.cells()
+            .iter()
+            .map(|cell| Candidate::new(cell, self.value()))
+            .collect()
+    }
+}
+
+impl From<Candidate> for Node {
+    fn from(cand: Candidate) -> Self {
+        Node::Single(cand)
+    }
 }
 
 // weak inference means start is false end is must be true
 // strong inference means start is true and end must be false
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InferenceType {
     Strong,
     Weak,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Inference {
-    pub start: Candidate,
-    pub end: Candidate,
+    pub start: Node,
+    pub end: Node,
     pub inference_type: InferenceType,
 }
 
 impl Inference {
-    pub fn new(start: Candidate, end: Candidate, inference_type: InferenceType) -> Self {
+    pub fn new(
+        start: impl Into<Node>,
+        end: impl Into<Node>,
+        inference_type: InferenceType,
+    ) -> Self {
         Inference {
-            start,
-            end,
+            start: start.into(),
+            end: end.into(),
             inference_type,
         }
     }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Chain {
     pub inferences: Vec<Inference>,
 }
@@ -61,13 +121,20 @@ impl Chain {
     pub fn len(&self) -> usize {
         self.inferences.len()
     }
+    pub fn is_grouped(&self) -> bool {
+        self.inferences
+            .iter()
+            .any(|inf| inf.start.is_group() || inf.end.is_group())
+    }
     pub fn cells_num(&self) -> usize {
         if self.inferences.is_empty() {
             return 0;
         }
-        let mut cells: HashSet<u8> = self.inferences.iter().map(|c| c.start.cell()).collect();
         let last = self.inferences.last().unwrap();
-        cells.insert(last.end.cell());
-        cells.len()
+        let cells = self
+            .inferences
+            .iter()
+            .fold(last.end.cells(), |u, inf| u.union(&inf.start.cells()));
+        cells.count() as usize
     }
 }
@@ -4,6 +4,7 @@ pub mod aic_type1;
 pub mod aic_type2;
 pub mod continuous_nice_loop;
 pub mod discontinuous_nice_loop;
+pub mod forcing_chain;
 pub mod graph;
 pub mod link;
 pub mod remote_pair;
@@ -11,6 +12,7 @@ pub mod x_chain;
 pub mod xy_chain;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ChainType {
     RemotePair,
     XChain,
@@ -22,6 +24,7 @@ pub enum ChainType {
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChainStep {
     pub chain_type: ChainType,
     pub chain: link::Chain,
@@ -35,18 +38,29 @@ impl ChainStep {
         }
     }
     pub fn name(&self) -> &str {
+        if self.chain.is_grouped() {
+            return match self.chain_type {
+                ChainType::RemotePair => "Remote Pair",
+                ChainType::XChain => "Grouped X-Chain",
+                ChainType::XYChain => "XY-Chain",
+                ChainType::ContinuousNiceLoop => "Grouped Continuous Nice Loop",
+                ChainType::DisContinuousNiceLoop => "Grouped Discontinuous Nice Loop",
+                ChainType::AicType1 => "Grouped AIC Type1",
+                ChainType::AicType2 => "Grouped AIC Type2",
+            };
+        }
         match self.chain_type {
             ChainType::RemotePair => "Remote Pair",
             ChainType::XChain => "X-Chain",
             ChainType::XYChain => "XY-Chain",
-            ChainType::ContinuousNiceLoop => "Continuouses Nice Loop",
-            ChainType::DisContinuousNiceLoop => "DisContinuous Nice Loop",
+            ChainType::ContinuousNiceLoop => "Continuous Nice Loop",
+            ChainType::DisContinuousNiceLoop => "Discontinuous Nice Loop",
             ChainType::AicType1 => "AIC Type1",
             ChainType::AicType2 => "AIC Type2",
         }
     }
     pub fn difficulty(&self) -> u32 {
-        match self.chain_type {
+        let difficulty = match self.chain_type {
             ChainType::XChain => 260,
             ChainType::XYChain => 260,
             ChainType::RemotePair => 110,
@@ -54,6 +68,80 @@ impl ChainStep {
             ChainType::DisContinuousNiceLoop => 280,
             ChainType::AicType1 => 470,
             ChainType::AicType2 => 470,
+        };
+        // group nodes are harder to spot
+        if self.chain.is_grouped() {
+            difficulty + 20
+        } else {
+            difficulty
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        grid::Grid,
+        solver::{
+            SolverStrategy,
+            bitboard::BitboardSolver,
+            chain::{
+                aic_type1::AicType1Finder, aic_type2::AicType2Finder,
+                continuous_nice_loop::ContinuousNiceLoopFinder,
+                discontinuous_nice_loop::DiscontinuousNiceLoopFinder,
+            },
+            step::Step,
+            step_accumulator::AllStepAccumulator,
+        },
+    };
+
+    #[test]
+    fn test_grouped_chains() {
+        let s = r#".--------------------.-------------------.-------------------.
+| 39    3569   359   | 7     2     8     | 4     1     356   |
+| 1     456    2     | 9     3     456   | 8     7     56    |
+| 348   34567  34578 | 1     46    456   | 25    2356  9     |
+:--------------------+-------------------+-------------------:
+| 6     8      34579 | 23    147   12347 | 2579  2345  23457 |
+| 234   23457  3457  | 238   478   9     | 6     2345  1     |
+| 2349  1      3479  | 236   5     23467 | 279   234   8     |
+:--------------------+-------------------+-------------------:
+| 7     239    389   | 4     689   236   | 1     2568  256   |
+| 248   24     6     | 5     178   127   | 3     9     247   |
+| 5     2349   1     | 2368  6789  2367  | 27    2468  2467  |
+'--------------------'-------------------'-------------------'"#;
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let solution = *BitboardSolver::new().solve(&grid).values();
+        let finders: [(&dyn SolverStrategy, &str); 4] = [
+            (
+                &DiscontinuousNiceLoopFinder::default(),
+                "Grouped Discontinuous Nice Loop",
+            ),
+            (
+                &ContinuousNiceLoopFinder::default(),
+                "Grouped Continuous Nice Loop",
+            ),
+            (&AicType1Finder::default(), "Grouped AIC Type1"),
+            (&AicType2Finder::default(), "Grouped AIC Type2"),
+        ];
+        for (finder, name) in finders {
+            let mut acc = AllStepAccumulator::default();
+            finder.find_step(&grid, &mut acc);
+            let grouped: Vec<&Step> = acc
+                .get_steps()
+                .iter()
+                .filter(|step| step.name() == name)
+                .collect();
+            assert!(!grouped.is_empty(), "no {}", name);
+            for step in grouped {
+                let Step::Chain(chain) = step else {
+                    panic!("not chain step");
+                };
+                assert!(chain.chain.is_grouped());
+                for cand in chain.remove_candidates.iter() {
+                    assert_ne!(solution[cand.cell() as usize], cand.value());
+                }
+            }
         }
     }
 }
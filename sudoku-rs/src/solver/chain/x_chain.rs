@@ -3,7 +3,7 @@ use std::collections::{HashSet, VecDeque};
 use crate::{
     candidate::Candidate,
     grid::Grid,
-    grid_constant::get_cell_buddies,
+    grid_constant::get_common_buddies,
     solver::{
         SolverStrategy,
         chain::{
@@ -14,6 +14,7 @@ use crate::{
         step::Step,
         step_accumulator::StepAccumulator,
     },
+    util::indexset::IndexSet,
 };
 
 #[derive(Default)]
@@ -45,14 +46,13 @@ impl XChainFinder {
             }
             while !queue.is_empty() {
                 let current_chain = queue.pop_back().unwrap();
-                let used: Vec<u8> = current_chain
+                let used = current_chain
                     .inferences
                     .iter()
-                    .map(|inf| inf.start.cell())
-                    .collect();
+                    .fold(IndexSet::new_empty(), |u, inf| u.union(&inf.start.cells()));
                 let last = current_chain.inferences.last().unwrap();
                 for edge in graph.edges[&last.end].iter() {
-                    if used.contains(&edge.end.cell()) {
+                    if !used.intersect(&edge.end.cells()).is_empty() {
                         continue;
                     }
                     let mut chain = current_chain.clone();
@@ -88,8 +88,8 @@ impl XChainFinder {
                         if first.inference_type == InferenceType::Strong
                             && last.inference_type == InferenceType::Strong
                         {
-                            let common_buddies = get_cell_buddies(first.start.cell())
-                                .intersect(&get_cell_buddies(last.end.cell()));
+                            let common_buddies =
+                                get_common_buddies(&first.start.cells().union(&last.end.cells()));
                             let remove_cells: Vec<u8> = common_buddies
                                 .iter()
                                 .filter(|c| grid.cell_has_candidate(*c, x))
@@ -164,7 +164,12 @@ mod test {
         for step in steps.iter() {
             println!("{:?}\n", step);
         }
-        // NOTE there is only one xchain , the other one is the reverse of the first
-        assert_eq!(steps.len(), 2);
+        // NOTE there is only one xchain , the other one is the reverse of the first,
+        // the others are grouped x-chains
+        assert_eq!(steps.len(), 12);
+        assert_eq!(
+            steps.iter().filter(|step| step.name() == "X-Chain").count(),
+            2
+        );
     }
 }
@@ -9,7 +9,7 @@ use crate::{
         chain::{
             ChainStep, ChainType,
             graph::Graph,
-            link::{Chain, Inference, InferenceType},
+            link::{Chain, Inference, InferenceType, Node},
         },
         step::Step,
         step_accumulator::StepAccumulator,
@@ -40,12 +40,12 @@ impl XYChainFinder {
         while !queue.is_empty() {
             let current_chain = queue.pop_front().unwrap();
             let last = current_chain.inferences.last().unwrap();
-            let used: HashSet<Candidate> = current_chain
+            let used: HashSet<Node> = current_chain
                 .inferences
                 .iter()
                 .map(|inf| inf.start.clone())
                 .collect();
-            let used_end: HashSet<Candidate> = current_chain
+            let used_end: HashSet<Node> = current_chain
                 .inferences
                 .iter()
                 .map(|inf| inf.end.clone())
@@ -0,0 +1,420 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    grid_constant::get_cell_buddies,
+    solver::{SolverStrategy, step::Step, step_accumulator::StepAccumulator},
+    util::format_step::{format_candidates_cells, format_candidates_values, format_cell},
+};
+
+/**
+3D Medusa colors candidates of all digits, the strong links are the conjugate pairs of a digit in
+a house and the two candidates of a bivalue cell. one of the two colors of a cluster is true
+- TwiceInCell: two candidates of the same color in one cell, the color is false
+- TwiceInHouse: a digit has the same color twice in a house, the color is false
+- TwoColorsInCell: a cell has both colors, the other candidates of the cell are false
+- TwoColorsElsewhere: a candidate sees the digit in both colors, it is false
+- TwoColorsUnitCell: a candidate sees the digit in one color and its cell has the other color
+- CellEmptiedByColor: all candidates of an uncolored cell see the digit in the same color, the
+  color is false
+**/
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MedusaRule {
+    TwiceInCell,
+    TwiceInHouse,
+    TwoColorsInCell,
+    TwoColorsElsewhere,
+    TwoColorsUnitCell,
+    CellEmptiedByColor,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Medusa {
+    pub rule: MedusaRule,
+    pub color1: Vec<Candidate>,
+    pub color2: Vec<Candidate>,
+    pub remove_candidates: Vec<Candidate>,
+    // the true color is set when the other color is false
+    pub set_values: Vec<Candidate>,
+}
+
+impl Medusa {
+    pub fn apply(&self, grid: &mut Grid) {
+        for cand in self.remove_candidates.iter() {
+            grid.remvoe_candidate(cand);
+        }
+        for cand in self.set_values.iter() {
+            grid.set_value_with_candidate(cand);
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        "3D Medusa"
+    }
+
+    pub fn difficulty(&self) -> u32 {
+        230
+    }
+
+    pub fn explain(&self) -> String {
+        let reason = match self.rule {
+            MedusaRule::TwiceInCell => "two candidates of one color are in the same cell",
+            MedusaRule::TwiceInHouse => "a digit has the same color twice in a house",
+            MedusaRule::TwoColorsInCell => "a cell has candidates of both colors",
+            MedusaRule::TwoColorsElsewhere => "candidates see the same digit in both colors",
+            MedusaRule::TwoColorsUnitCell => {
+                "candidates see the same digit in one color and share the cell with the other color"
+            }
+            MedusaRule::CellEmptiedByColor => {
+                "all candidates of a cell would be removed by one color"
+            }
+        };
+        let mut res = format!(
+            "<h3>{}</h3><p>color 1: {}<br>color 2: {}<br>{}",
+            self.name(),
+            format_colored(&self.color1),
+            format_colored(&self.color2),
+            reason
+        );
+        if !self.set_values.is_empty() {
+            res.push_str(
+                format!(
+                    ", the color is false and the other color is true, {} can be set",
+                    format_colored(&self.set_values)
+                )
+                .as_str(),
+            );
+        }
+        if !self.remove_candidates.is_empty() {
+            res.push_str(
+                format!(
+                    ", {} can be removed from {}",
+                    format_candidates_values(&self.remove_candidates),
+                    format_candidates_cells(&self.remove_candidates)
+                )
+                .as_str(),
+            );
+        }
+        res.push_str("</p>");
+        res
+    }
+}
+
+fn format_colored(cands: &[Candidate]) -> String {
+    cands
+        .iter()
+        .map(|c| format!("<b>{}{}</b>", c.value(), format_cell(c.cell())))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+#[derive(Default)]
+pub struct MedusaFinder {}
+
+// the two colors of a cluster, keyed by candidate
+struct Cluster {
+    colors: BTreeMap<Candidate, usize>,
+}
+
+impl Cluster {
+    fn candidates(&self, color: usize) -> Vec<Candidate> {
+        self.colors
+            .iter()
+            .filter(|(_, c)| **c == color)
+            .map(|(cand, _)| *cand)
+            .collect()
+    }
+
+    fn color(&self, cand: &Candidate) -> Option<usize> {
+        self.colors.get(cand).copied()
+    }
+
+    fn cell_colors(&self, grid: &Grid, cell: u8) -> Vec<(u8, usize)> {
+        grid.get_cell_candidate(cell)
+            .iter()
+            .filter_map(|v| Some((v, self.color(&Candidate::new(cell, v))?)))
+            .collect()
+    }
+
+    // the colors of the digit in the buddies of the cell
+    fn seen_colors(&self, cell: u8, value: u8) -> [bool; 2] {
+        let mut seen = [false; 2];
+        for buddy in get_cell_buddies(cell).iter() {
+            if let Some(color) = self.color(&Candidate::new(buddy, value)) {
+                seen[color] = true;
+            }
+        }
+        seen
+    }
+}
+
+impl MedusaFinder {
+    fn strong_links(&self, grid: &Grid) -> BTreeMap<Candidate, Vec<Candidate>> {
+        let mut links: BTreeMap<Candidate, Vec<Candidate>> = BTreeMap::new();
+        let mut add_link = |a: Candidate, b: Candidate| {
+            links.entry(a).or_default().push(b);
+            links.entry(b).or_default().push(a);
+        };
+        for cell in 0..81 {
+            let values = grid.get_cell_candidate(cell).values();
+            if grid.get_value(cell) == 0 && values.len() == 2 {
+                add_link(
+                    Candidate::new(cell, values[0]),
+                    Candidate::new(cell, values[1]),
+                );
+            }
+        }
+        for house in 0..27 {
+            for value in 1..=9 {
+                let cells = grid.pential_cells_in_house(house, value).values();
+                if cells.len() == 2 {
+                    add_link(
+                        Candidate::new(cells[0], value),
+                        Candidate::new(cells[1], value),
+                    );
+                }
+            }
+        }
+        links
+    }
+
+    fn find_clusters(&self, grid: &Grid) -> Vec<Cluster> {
+        let links = self.strong_links(grid);
+        let mut colored: BTreeMap<Candidate, usize> = BTreeMap::new();
+        let mut clusters = Vec::new();
+        for start in links.keys() {
+            if colored.contains_key(start) {
+                continue;
+            }
+            let mut cluster = Cluster {
+                colors: BTreeMap::new(),
+            };
+            let mut stack = vec![(*start, 0)];
+            colored.insert(*start, 0);
+            while let Some((cand, color)) = stack.pop() {
+                cluster.colors.insert(cand, color);
+                for end in links[&cand].iter() {
+                    if colored.contains_key(end) {
+                        continue;
+                    }
+                    colored.insert(*end, 1 - color);
+                    stack.push((*end, 1 - color));
+                }
+            }
+            clusters.push(cluster);
+        }
+        clusters
+    }
+
+    fn add_step(
+        &self,
+        cluster: &Cluster,
+        rule: MedusaRule,
+        remove_candidates: Vec<Candidate>,
+        set_values: Vec<Candidate>,
+        acc: &mut dyn StepAccumulator,
+    ) -> bool {
+        let step = Medusa {
+            rule,
+            color1: cluster.candidates(0),
+            color2: cluster.candidates(1),
+            remove_candidates,
+            set_values,
+        };
+        acc.add_step(Step::Medusa(step))
+    }
+
+    // the color is false, the other color is true
+    fn add_false_color(
+        &self,
+        cluster: &Cluster,
+        rule: MedusaRule,
+        color: usize,
+        acc: &mut dyn StepAccumulator,
+    ) -> bool {
+        self.add_step(
+            cluster,
+            rule,
+            cluster.candidates(color),
+            cluster.candidates(1 - color),
+            acc,
+        )
+    }
+
+    // rule 1, 2 and 6 find a false color
+    fn find_false_color(
+        &self,
+        grid: &Grid,
+        cluster: &Cluster,
+        acc: &mut dyn StepAccumulator,
+    ) -> bool {
+        for color in 0..2 {
+            let cands = cluster.candidates(color);
+            let twice_in_cell = cands
+                .iter()
+                .enumerate()
+                .any(|(i, a)| cands[i + 1..].iter().any(|b| a.cell() == b.cell()));
+            if twice_in_cell && self.add_false_color(cluster, MedusaRule::TwiceInCell, color, acc) {
+                return true;
+            }
+        }
+        for color in 0..2 {
+            let cands = cluster.candidates(color);
+            let twice_in_house = cands.iter().any(|a| {
+                get_cell_buddies(a.cell())
+                    .iter()
+                    .any(|buddy| cands.contains(&Candidate::new(buddy, a.value())))
+            });
+            if twice_in_house && self.add_false_color(cluster, MedusaRule::TwiceInHouse, color, acc)
+            {
+                return true;
+            }
+        }
+        for cell in 0..81 {
+            if grid.get_value(cell) != 0 || !cluster.cell_colors(grid, cell).is_empty() {
+                continue;
+            }
+            for color in 0..2 {
+                let emptied = grid
+                    .get_cell_candidate(cell)
+                    .iter()
+                    .all(|v| cluster.seen_colors(cell, v)[color]);
+                if emptied
+                    && self.add_false_color(cluster, MedusaRule::CellEmptiedByColor, color, acc)
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    // rule 3, 4 and 5 remove uncolored candidates
+    fn find_eliminations(
+        &self,
+        grid: &Grid,
+        cluster: &Cluster,
+        acc: &mut dyn StepAccumulator,
+    ) -> bool {
+        let mut removes: [Vec<Candidate>; 3] = [vec![], vec![], vec![]];
+        for cell in 0..81 {
+            if grid.get_value(cell) != 0 {
+                continue;
+            }
+            let cell_colors = cluster.cell_colors(grid, cell);
+            let has_color = |color: usize| cell_colors.iter().any(|(_, c)| *c == color);
+            for value in grid.get_cell_candidate(cell).iter() {
+                let cand = Candidate::new(cell, value);
+                if cluster.color(&cand).is_some() {
+                    continue;
+                }
+                let seen = cluster.seen_colors(cell, value);
+                if has_color(0) && has_color(1) {
+                    removes[0].push(cand);
+                } else if seen[0] && seen[1] {
+                    removes[1].push(cand);
+                } else if (seen[0] && has_color(1)) || (seen[1] && has_color(0)) {
+                    removes[2].push(cand);
+                }
+            }
+        }
+        let rules = [
+            MedusaRule::TwoColorsInCell,
+            MedusaRule::TwoColorsElsewhere,
+            MedusaRule::TwoColorsUnitCell,
+        ];
+        for (rule, remove_candidates) in rules.into_iter().zip(removes) {
+            if !remove_candidates.is_empty()
+                && self.add_step(cluster, rule, remove_candidates, vec![], acc)
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn find_medusa(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        for cluster in self.find_clusters(grid) {
+            // two candidates are a conjugate pair or a bivalue cell, nothing to find
+            if cluster.colors.len() < 3 {
+                continue;
+            }
+            if self.find_false_color(grid, &cluster, acc) {
+                return;
+            }
+            if self.find_eliminations(grid, &cluster, acc) {
+                return;
+            }
+        }
+    }
+}
+
+impl SolverStrategy for MedusaFinder {
+    fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        self.find_medusa(grid, acc);
+    }
+    fn name(&self) -> &str {
+        "MedusaFinder"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        candidate::Candidate,
+        grid::Grid,
+        solver::{
+            SolverStrategy,
+            bitboard::BitboardSolver,
+            coloring::medusa::{MedusaFinder, MedusaRule},
+            step::Step,
+            step_accumulator::AllStepAccumulator,
+        },
+    };
+
+    #[test]
+    fn test_medusa() {
+        let s = ".-----------.---------------.------------------.
+| 4   39  2 | 7   13   1358 | 1569   1589  136 |
+| 8   39  7 | 15  4    6    | 1259   1259  123 |
+| 5   6   1 | 9   23   238  | 4      28    7   |
+:-----------+---------------+------------------:
+| 29  1   3 | 6   79   279  | 8      4     5   |
+| 29  7   8 | 4   5    129  | 3      6     12  |
+| 6   5   4 | 8   123  123  | 12     7     9   |
+:-----------+---------------+------------------:
+| 7   8   5 | 2   6    19   | 19     3     4   |
+| 1   2   6 | 3   79   4    | 579    59    8   |
+| 3   4   9 | 15  8    157  | 12567  125   126 |
+'-----------'---------------'------------------'";
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let solution = *BitboardSolver::new().solve(&grid).values();
+        let finder = MedusaFinder::default();
+        let mut acc = AllStepAccumulator::default();
+        finder.find_step(&grid, &mut acc);
+        let steps = acc.get_steps();
+        assert!(!steps.is_empty());
+        let mut found = false;
+        for step in steps.iter() {
+            let Step::Medusa(medusa) = step else {
+                panic!("not medusa step");
+            };
+            for cand in medusa.remove_candidates.iter() {
+                assert_ne!(solution[cand.cell() as usize], cand.value());
+            }
+            for cand in medusa.set_values.iter() {
+                assert_eq!(solution[cand.cell() as usize], cand.value());
+            }
+            // 2 and 3 of r3c6 have different colors, one of them is the value
+            if medusa.rule == MedusaRule::TwoColorsInCell
+                && medusa.remove_candidates.contains(&Candidate::new(23, 8))
+            {
+                found = true;
+            }
+        }
+        assert!(found);
+    }
+}
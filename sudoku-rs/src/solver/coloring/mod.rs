@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    grid_constant::get_cell_buddies,
+    solver::chain::{
+        graph::Graph,
+        link::{LinkType, Node},
+    },
+    util::{
+        format_step::{format_candidates_cells, format_candidates_values},
+        indexset::IndexSet,
+    },
+};
+
+pub mod medusa;
+pub mod multi_colors;
+pub mod simple_colors;
+
+/**
+coloring works on one digit, candidates connected by strong links (conjugate pairs) form a cluster,
+every cluster is colored with two colors alternately, one of the two colors must be true
+**/
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorCluster {
+    pub color1: Vec<Candidate>,
+    pub color2: Vec<Candidate>,
+}
+
+impl ColorCluster {
+    pub fn cells(&self, color: usize) -> IndexSet {
+        let cands = if color == 0 {
+            &self.color1
+        } else {
+            &self.color2
+        };
+        IndexSet::new_from_values(cands.iter().map(|c| c.cell()))
+    }
+
+    pub fn all_cells(&self) -> IndexSet {
+        self.cells(0).union(&self.cells(1))
+    }
+
+    pub fn candidates(&self, color: usize) -> &[Candidate] {
+        if color == 0 {
+            &self.color1
+        } else {
+            &self.color2
+        }
+    }
+}
+
+// cells which see at least one cell of the set
+pub fn see_any(cells: &IndexSet) -> IndexSet {
+    cells.iter().fold(IndexSet::new_empty(), |u, cell| {
+        u.union(&get_cell_buddies(cell))
+    })
+}
+
+// clusters of value built from conjugate pairs, group nodes are not used
+pub fn find_clusters(grid: &Grid, value: u8) -> Vec<ColorCluster> {
+    let graph = Graph::new_x_chain_graph(grid, value);
+    let mut strong_links: HashMap<Candidate, Vec<Candidate>> = HashMap::new();
+    for (start, edges) in graph.edges.iter() {
+        let Node::Single(start) = start else {
+            continue;
+        };
+        for edge in edges.iter() {
+            if edge.link_type != LinkType::Strong {
+                continue;
+            }
+            if let Node::Single(end) = edge.end {
+                strong_links.entry(*start).or_default().push(end);
+            }
+        }
+    }
+    let mut starts: Vec<Candidate> = strong_links.keys().copied().collect();
+    starts.sort_by_key(|c| c.cell());
+    let mut colored: HashMap<Candidate, usize> = HashMap::new();
+    let mut clusters = Vec::new();
+    for start in starts {
+        if colored.contains_key(&start) {
+            continue;
+        }
+        let mut cluster = ColorCluster {
+            color1: Vec::new(),
+            color2: Vec::new(),
+        };
+        let mut stack = vec![(start, 0)];
+        colored.insert(start, 0);
+        while let Some((cand, color)) = stack.pop() {
+            if color == 0 {
+                cluster.color1.push(cand);
+            } else {
+                cluster.color2.push(cand);
+            }
+            for end in strong_links[&cand].iter() {
+                if colored.contains_key(end) {
+                    continue;
+                }
+                colored.insert(*end, 1 - color);
+                stack.push((*end, 1 - color));
+            }
+        }
+        cluster.color1.sort_by_key(|c| c.cell());
+        cluster.color2.sort_by_key(|c| c.cell());
+        clusters.push(cluster);
+    }
+    clusters
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColoringType {
+    ColorTrap,
+    ColorWrap,
+    MultiColors1,
+    MultiColors2,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coloring {
+    pub coloring_type: ColoringType,
+    pub value: u8,
+    // one cluster for simple colors, two for multi colors
+    pub clusters: Vec<ColorCluster>,
+    pub remove_candidates: Vec<Candidate>,
+}
+
+impl Coloring {
+    pub fn apply(&self, grid: &mut Grid) {
+        for cand in self.remove_candidates.iter() {
+            grid.remvoe_candidate(cand);
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self.coloring_type {
+            ColoringType::ColorTrap => "Simple Colors Trap",
+            ColoringType::ColorWrap => "Simple Colors Wrap",
+            ColoringType::MultiColors1 => "Multi Colors 1",
+            ColoringType::MultiColors2 => "Multi Colors 2",
+        }
+    }
+
+    pub fn difficulty(&self) -> u32 {
+        match self.coloring_type {
+            ColoringType::ColorTrap | ColoringType::ColorWrap => 150,
+            ColoringType::MultiColors1 | ColoringType::MultiColors2 => 200,
+        }
+    }
+
+    pub fn explain(&self) -> String {
+        let mut res = format!("<h3>{}</h3><p>", self.name());
+        for (i, cluster) in self.clusters.iter().enumerate() {
+            res.push_str(
+                format!(
+                    "cluster {} of <b>{}</b>: {} and {}<br>",
+                    i + 1,
+                    self.value,
+                    format_candidates_cells(&cluster.color1),
+                    format_candidates_cells(&cluster.color2)
+                )
+                .as_str(),
+            );
+        }
+        let reason = match self.coloring_type {
+            ColoringType::ColorTrap => {
+                "one of the two colors must be true, candidates see both colors"
+            }
+            ColoringType::ColorWrap => {
+                "two cells of the same color see each other, the color is false"
+            }
+            ColoringType::MultiColors1 => {
+                "a color of each cluster see each other, one of the other two colors must be true"
+            }
+            ColoringType::MultiColors2 => {
+                "a color sees both colors of the other cluster, it is false"
+            }
+        };
+        res.push_str(
+            format!(
+                "{}, {} can be removed from {}</p>",
+                reason,
+                format_candidates_values(&self.remove_candidates),
+                format_candidates_cells(&self.remove_candidates)
+            )
+            .as_str(),
+        );
+        res
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    solver::{
+        SolverStrategy,
+        coloring::{ColorCluster, Coloring, ColoringType, find_clusters, see_any},
+        step::Step,
+        step_accumulator::StepAccumulator,
+    },
+};
+
+#[derive(Default)]
+pub struct MultiColorsFinder {}
+
+impl MultiColorsFinder {
+    // a color of cluster a sees a color of cluster b, they can not be both true, so one of the
+    // other two colors is true
+    fn find_multi_colors1(
+        &self,
+        grid: &Grid,
+        value: u8,
+        a: &ColorCluster,
+        b: &ColorCluster,
+        acc: &mut dyn StepAccumulator,
+    ) -> bool {
+        for color_a in 0..2 {
+            for color_b in 0..2 {
+                if see_any(&a.cells(color_a))
+                    .intersect(&b.cells(color_b))
+                    .is_empty()
+                {
+                    continue;
+                }
+                let remove_candidates: Vec<Candidate> = see_any(&a.cells(1 - color_a))
+                    .intersect(&see_any(&b.cells(1 - color_b)))
+                    .difference(&a.all_cells().union(&b.all_cells()))
+                    .iter()
+                    .filter(|cell| grid.cell_has_candidate(*cell, value))
+                    .map(|cell| Candidate::new(cell, value))
+                    .collect();
+                if remove_candidates.is_empty() {
+                    continue;
+                }
+                let step = Coloring {
+                    coloring_type: ColoringType::MultiColors1,
+                    value,
+                    clusters: vec![a.clone(), b.clone()],
+                    remove_candidates,
+                };
+                if acc.add_step(Step::Coloring(step)) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    // a color of cluster a sees both colors of cluster b, the color is false
+    fn find_multi_colors2(
+        &self,
+        value: u8,
+        a: &ColorCluster,
+        b: &ColorCluster,
+        acc: &mut dyn StepAccumulator,
+    ) -> bool {
+        for color_a in 0..2 {
+            let seen = see_any(&a.cells(color_a));
+            if seen.intersect(&b.cells(0)).is_empty() || seen.intersect(&b.cells(1)).is_empty() {
+                continue;
+            }
+            let step = Coloring {
+                coloring_type: ColoringType::MultiColors2,
+                value,
+                clusters: vec![a.clone(), b.clone()],
+                remove_candidates: a.candidates(color_a).to_vec(),
+            };
+            if acc.add_step(Step::Coloring(step)) {
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn find_multi_colors(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        for value in 1..=9 {
+            let clusters = find_clusters(grid, value);
+            for (i, a) in clusters.iter().enumerate() {
+                for (j, b) in clusters.iter().enumerate() {
+                    if i == j {
+                        continue;
+                    }
+                    if i < j && self.find_multi_colors1(grid, value, a, b, acc) {
+                        return;
+                    }
+                    if self.find_multi_colors2(value, a, b, acc) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl SolverStrategy for MultiColorsFinder {
+    fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        self.find_multi_colors(grid, acc);
+    }
+    fn name(&self) -> &str {
+        "MultiColorsFinder"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        grid::Grid,
+        solver::{
+            SolverStrategy, coloring::multi_colors::MultiColorsFinder,
+            step_accumulator::AllStepAccumulator,
+        },
+    };
+
+    #[test]
+    fn test_multi_colors() {
+        let s = r#".--------------------------.---------------------.-----------------------.
+| 2346789  2345678  356789 | 46789  56789  45689 | 23679   123469  12467 |
+| 24679    2467     679    | 1      679    3     | 2679    8       5     |
+| 346789   345678   1      | 46789  2      45689 | 3679    3469    467   |
+:--------------------------+---------------------+-----------------------:
+| 1        2368     368    | 5      3689   7     | 23689   23469   2468  |
+| 23678    235678   4      | 23689  3689   2689  | 1       23569   2678  |
+| 23678    9        35678  | 23468  368    1     | 235678  23456   24678 |
+:--------------------------+---------------------+-----------------------:
+| 5        1        689    | 2689   689    2689  | 4       7       3     |
+| 346789   34678    2      | 36789  1      5689  | 568     56      68    |
+| 3678     3678     3678   | 23678  4      2568  | 2568    1256    9     |
+'--------------------------'---------------------'-----------------------'"#;
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let solver = MultiColorsFinder::default();
+        let mut acc = AllStepAccumulator::default();
+        solver.find_step(&grid, &mut acc);
+        let steps = acc.get_steps();
+        assert_eq!(steps.len(), 3);
+        assert_eq!(
+            steps
+                .iter()
+                .filter(|step| step.name() == "Multi Colors 2")
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    solver::{
+        SolverStrategy,
+        coloring::{ColorCluster, Coloring, ColoringType, find_clusters, see_any},
+        step::Step,
+        step_accumulator::StepAccumulator,
+    },
+};
+
+#[derive(Default)]
+pub struct SimpleColorsFinder {}
+
+impl SimpleColorsFinder {
+    // two cells of the same color in one house, the color is false
+    fn find_color_wrap(
+        &self,
+        value: u8,
+        cluster: &ColorCluster,
+        acc: &mut dyn StepAccumulator,
+    ) -> bool {
+        for color in 0..2 {
+            let cells = cluster.cells(color);
+            if see_any(&cells).intersect(&cells).is_empty() {
+                continue;
+            }
+            let step = Coloring {
+                coloring_type: ColoringType::ColorWrap,
+                value,
+                clusters: vec![cluster.clone()],
+                remove_candidates: cluster.candidates(color).to_vec(),
+            };
+            if acc.add_step(Step::Coloring(step)) {
+                return true;
+            }
+        }
+        false
+    }
+
+    // candidates see both colors
+    fn find_color_trap(
+        &self,
+        grid: &Grid,
+        value: u8,
+        cluster: &ColorCluster,
+        acc: &mut dyn StepAccumulator,
+    ) -> bool {
+        // a single conjugate pair is a locked candidate
+        if cluster.color1.len() + cluster.color2.len() < 3 {
+            return false;
+        }
+        let remove_candidates: Vec<Candidate> = see_any(&cluster.cells(0))
+            .intersect(&see_any(&cluster.cells(1)))
+            .difference(&cluster.all_cells())
+            .iter()
+            .filter(|cell| grid.cell_has_candidate(*cell, value))
+            .map(|cell| Candidate::new(cell, value))
+            .collect();
+        if remove_candidates.is_empty() {
+            return false;
+        }
+        let step = Coloring {
+            coloring_type: ColoringType::ColorTrap,
+            value,
+            clusters: vec![cluster.clone()],
+            remove_candidates,
+        };
+        acc.add_step(Step::Coloring(step))
+    }
+
+    pub fn find_simple_colors(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        for value in 1..=9 {
+            for cluster in find_clusters(grid, value) {
+                if self.find_color_trap(grid, value, &cluster, acc) {
+                    return;
+                }
+                if self.find_color_wrap(value, &cluster, acc) {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl SolverStrategy for SimpleColorsFinder {
+    fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        self.find_simple_colors(grid, acc);
+    }
+    fn name(&self) -> &str {
+        "SimpleColorsFinder"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        grid::Grid,
+        solver::{
+            SolverStrategy,
+            coloring::{ColoringType, simple_colors::SimpleColorsFinder},
+            step::Step,
+            step_accumulator::AllStepAccumulator,
+        },
+    };
+
+    #[test]
+    fn test_simple_colors() {
+        let s = r#".-----------------------.------------------.------------------.
+| 2346789  234678  3678 | 4689   5     468 | 3679  2349  1    |
+| 2469     246     6    | 1      7     3   | 69    8     5    |
+| 346789   5       1    | 4689   2     468 | 3679  349   467  |
+:-----------------------+------------------+------------------:
+| 1        2368    368  | 5      3689  7   | 3689  2349  246  |
+| 23678    23678   4    | 23689  3689  268 | 1     5     267  |
+| 23678    9       5    | 23468  368   1   | 3678  234   2467 |
+:-----------------------+------------------+------------------:
+| 5        1       9    | 268    68    268 | 4     7     3    |
+| 347      347     2    | 37     1     9   | 5     6     8    |
+| 3678     3678    3678 | 3678   4     5   | 2     1     9    |
+'-----------------------'------------------'------------------'"#;
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let solver = SimpleColorsFinder::default();
+        let mut acc = AllStepAccumulator::default();
+        solver.find_step(&grid, &mut acc);
+        let steps = acc.get_steps();
+        assert_eq!(steps.len(), 1);
+        assert!(steps.iter().all(|step| match step {
+            Step::Coloring(coloring) => coloring.coloring_type == ColoringType::ColorWrap,
+            _ => false,
+        }));
+    }
+}
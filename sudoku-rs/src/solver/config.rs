@@ -0,0 +1,784 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    error::{Result, SudokuError},
+    solver::{
+        SolverStrategy, aligned_exclusion, als, avoidable_rectangle_1, avoidable_rectangle_2,
+        bent_set, bug_plus_one, chain, coloring, deadly_pattern, empty_rectangle, fish, full_house,
+        hidden_rectangle, hidden_set, hidden_single, locked_candidate, locked_set, naked_set,
+        naked_single, skyscraper, sue_de_coq, two_string_kit, unique_1, unique_2, unique_3,
+        unique_4, unique_5, unique_6, wwing, xywing,
+    },
+};
+
+// every strategy known by the solver in the default order
+pub const STRATEGY_NAMES: [&str; 87] = [
+    "Full House",
+    "Naked Single",
+    "Hidden Single",
+    "Locked Pair",
+    "Locked Triple",
+    "Naked Pair",
+    "Naked Triple",
+    "Hidden Pair",
+    "Hidden Triple",
+    "Locked Candidate Pointing",
+    "Locked Candidate Claiming",
+    "Avoidable Rectangle Type1",
+    "Avoidable Rectangle Type2",
+    "Unique Type1",
+    "Unique Type2",
+    "Unique Type3",
+    "Unique Type4",
+    "Unique Type5",
+    "Unique Type6",
+    "Empty Rectangle",
+    "Hidden Rectangle",
+    "Bug Plus One",
+    "Extended Unique Rectangle",
+    "Unique Loop",
+    "BUG-Lite",
+    "Skyscraper",
+    "Sue de Coq",
+    "X-Wing",
+    "Remote Pair",
+    "Two String Kit",
+    "Swordfish",
+    "Jellyfish",
+    "XY-Wing",
+    "W-Wing",
+    "XYZ-Wing",
+    "WXYZ-Wing",
+    "VWXYZ-Wing",
+    "Finned X-Wing",
+    "Sashimi X-Wing",
+    "Finned Swordfish",
+    "Sashimi Swordfish",
+    "Finned Jellyfish",
+    "Sashimi Jellyfish",
+    "Squirmbag",
+    "Whale",
+    "Leviathan",
+    "Finned Squirmbag",
+    "Sashimi Squirmbag",
+    "Finned Whale",
+    "Sashimi Whale",
+    "Finned Leviathan",
+    "Sashimi Leviathan",
+    "Simple Colors",
+    "Multi Colors",
+    "3D Medusa",
+    "X-Chain",
+    "XY-Chain",
+    "Discontinuous Nice Loop",
+    "Continuous Nice Loop",
+    "AIC Type1",
+    "AIC Type2",
+    "ALS-XZ",
+    "ALS-XY-Wing",
+    "ALS-Chain",
+    "Death Blossom",
+    "Aligned Pair Exclusion",
+    "Aligned Triple Exclusion",
+    "Franken X-Wing",
+    "Franken Swordfish",
+    "Franken Jellyfish",
+    "Mutant X-Wing",
+    "Mutant Swordfish",
+    "Franken Squirmbag",
+    "Franken Whale",
+    "Franken Leviathan",
+    "Mutant Jellyfish",
+    "Mutant Squirmbag",
+    "Mutant Whale",
+    "Mutant Leviathan",
+    "Kraken X-Wing",
+    "Kraken Swordfish",
+    "Kraken Jellyfish",
+    "Kraken Squirmbag",
+    "Kraken Whale",
+    "Kraken Leviathan",
+    "Forcing Chain",
+    "Forcing Net",
+];
+
+static BEGINNER_STRATEGIES: [&str; 9] = [
+    "Full House",
+    "Naked Single",
+    "Hidden Single",
+    "Locked Pair",
+    "Locked Triple",
+    "Naked Pair",
+    "Naked Triple",
+    "Hidden Pair",
+    "Hidden Triple",
+];
+
+// the fish of size 5 to 7 and the larger mutant fish, rarely found and slow, disabled by default
+static LARGE_FISH: [&str; 19] = [
+    "Squirmbag",
+    "Whale",
+    "Leviathan",
+    "Finned Squirmbag",
+    "Sashimi Squirmbag",
+    "Finned Whale",
+    "Sashimi Whale",
+    "Finned Leviathan",
+    "Sashimi Leviathan",
+    "Franken Squirmbag",
+    "Franken Whale",
+    "Franken Leviathan",
+    "Mutant Jellyfish",
+    "Mutant Squirmbag",
+    "Mutant Whale",
+    "Mutant Leviathan",
+    "Kraken Squirmbag",
+    "Kraken Whale",
+    "Kraken Leviathan",
+];
+
+// the slowest strategies, they can take seconds on one grid
+static SLOW_STRATEGIES: [&str; 11] = [
+    "ALS-Chain",
+    "Death Blossom",
+    "Franken X-Wing",
+    "Franken Swordfish",
+    "Franken Jellyfish",
+    "Mutant X-Wing",
+    "Mutant Swordfish",
+    "Kraken X-Wing",
+    "Kraken Swordfish",
+    "Kraken Jellyfish",
+    "Forcing Net",
+];
+
+// the order of the default solver steps of HoDoKu, WXYZ-Wing and VWXYZ-Wing are not in HoDoKu
+static HODOKU_STRATEGIES: [&str; 62] = [
+    "Full House",
+    "Naked Single",
+    "Hidden Single",
+    "Locked Pair",
+    "Locked Triple",
+    "Locked Candidate Pointing",
+    "Locked Candidate Claiming",
+    "Naked Pair",
+    "Naked Triple",
+    "Hidden Pair",
+    "Hidden Triple",
+    "X-Wing",
+    "Swordfish",
+    "Jellyfish",
+    "Remote Pair",
+    "Bug Plus One",
+    "Skyscraper",
+    "Two String Kit",
+    "Empty Rectangle",
+    "W-Wing",
+    "XY-Wing",
+    "XYZ-Wing",
+    "Unique Type1",
+    "Unique Type2",
+    "Unique Type3",
+    "Unique Type4",
+    "Unique Type5",
+    "Unique Type6",
+    "Hidden Rectangle",
+    "Avoidable Rectangle Type1",
+    "Avoidable Rectangle Type2",
+    "Finned X-Wing",
+    "Sashimi X-Wing",
+    "Finned Swordfish",
+    "Sashimi Swordfish",
+    "Finned Jellyfish",
+    "Sashimi Jellyfish",
+    "Sue de Coq",
+    "Simple Colors",
+    "Multi Colors",
+    "X-Chain",
+    "XY-Chain",
+    "Discontinuous Nice Loop",
+    "Continuous Nice Loop",
+    "AIC Type1",
+    "AIC Type2",
+    "ALS-XZ",
+    "ALS-XY-Wing",
+    "ALS-Chain",
+    "Death Blossom",
+    "Aligned Pair Exclusion",
+    "Aligned Triple Exclusion",
+    "Franken X-Wing",
+    "Franken Swordfish",
+    "Franken Jellyfish",
+    "Mutant X-Wing",
+    "Mutant Swordfish",
+    "Kraken X-Wing",
+    "Kraken Swordfish",
+    "Kraken Jellyfish",
+    "Forcing Chain",
+    "Forcing Net",
+];
+
+// the default scores of HoDoKu for the step names of this solver, AICs are nice loops in HoDoKu
+static HODOKU_SCORES: [(&str, u32); 93] = [
+    ("Full House", 4),
+    ("Naked Single", 4),
+    ("Hidden Single", 14),
+    ("Locked Pair", 40),
+    ("Locked Triple", 60),
+    ("Locked Candidate", 50),
+    ("Naked Pair", 60),
+    ("Naked Triple", 80),
+    ("Naked Quadruple", 120),
+    ("Hidden Pair", 70),
+    ("Hidden Triple", 100),
+    ("Hidden Quadruple", 150),
+    ("X-Wing", 140),
+    ("Swordfish", 150),
+    ("Jellyfish", 160),
+    ("Remote Pair", 110),
+    ("Bug Plus One", 100),
+    ("Skyscraper", 130),
+    ("Two String Kit", 150),
+    ("Empty Rectangle", 120),
+    ("W-Wing", 150),
+    ("XY-Wing", 160),
+    ("XYZ-Wing", 180),
+    ("Unique Type1", 100),
+    ("Unique Type2", 100),
+    ("Unique Type3", 100),
+    ("Unique Type4", 100),
+    ("Unique Type5", 100),
+    ("Unique Type6", 100),
+    ("Hidden Rectangle", 100),
+    ("Avoidable Rectangle Type1", 100),
+    ("Avoidable Rectangle Type2", 100),
+    ("Finned X-Wing", 130),
+    ("Sashimi X-Wing", 150),
+    ("Finned Swordfish", 200),
+    ("Sashimi Swordfish", 240),
+    ("Finned Jellyfish", 250),
+    ("Sashimi Jellyfish", 260),
+    ("Sue de Coq", 250),
+    ("Simple Colors Trap", 150),
+    ("Simple Colors Wrap", 150),
+    ("Multi Colors 1", 200),
+    ("Multi Colors 2", 200),
+    ("X-Chain", 260),
+    ("XY-Chain", 260),
+    ("Grouped X-Chain", 300),
+    ("Discontinuous Nice Loop", 280),
+    ("Continuous Nice Loop", 280),
+    ("AIC Type1", 280),
+    ("AIC Type2", 280),
+    ("Grouped Discontinuous Nice Loop", 300),
+    ("Grouped Continuous Nice Loop", 300),
+    ("Grouped AIC Type1", 300),
+    ("Grouped AIC Type2", 300),
+    ("ALS-XZ", 300),
+    ("ALS-XY-Wing", 320),
+    ("ALS-Chain", 340),
+    ("Death Blossom", 360),
+    ("Aligned Pair Exclusion", 250),
+    ("Aligned Triple Exclusion", 270),
+    ("Franken X-Wing", 300),
+    ("Franken Swordfish", 350),
+    ("Franken Jellyfish", 370),
+    ("Finned Franken X-Wing", 390),
+    ("Finned Franken Swordfish", 410),
+    ("Finned Franken Jellyfish", 430),
+    ("Mutant X-Wing", 450),
+    ("Mutant Swordfish", 450),
+    ("Finned Mutant X-Wing", 470),
+    ("Finned Mutant Swordfish", 470),
+    ("Squirmbag", 470),
+    ("Whale", 470),
+    ("Leviathan", 470),
+    ("Finned Squirmbag", 470),
+    ("Sashimi Squirmbag", 470),
+    ("Finned Whale", 470),
+    ("Sashimi Whale", 470),
+    ("Finned Leviathan", 470),
+    ("Sashimi Leviathan", 470),
+    ("Franken Squirmbag", 470),
+    ("Franken Whale", 470),
+    ("Franken Leviathan", 470),
+    ("Finned Franken Squirmbag", 470),
+    ("Finned Franken Whale", 470),
+    ("Finned Franken Leviathan", 470),
+    ("Mutant Jellyfish", 450),
+    ("Mutant Squirmbag", 470),
+    ("Mutant Whale", 470),
+    ("Mutant Leviathan", 470),
+    ("Finned Mutant Jellyfish", 470),
+    ("Finned Mutant Squirmbag", 470),
+    ("Finned Mutant Whale", 470),
+    ("Finned Mutant Leviathan", 470),
+];
+
+// kraken fish and forcing chains have one score for all kinds in HoDoKu
+static HODOKU_GROUP_SCORES: [(&[&str], u32); 3] = [
+    (
+        &[
+            "Kraken X-Wing",
+            "Kraken Swordfish",
+            "Kraken Jellyfish",
+            "Kraken Squirmbag",
+            "Kraken Whale",
+            "Kraken Leviathan",
+        ],
+        500,
+    ),
+    (
+        &["Forcing Chain Contradiction", "Forcing Chain Verity"],
+        500,
+    ),
+    (&["Forcing Net Contradiction", "Forcing Net Verity"], 700),
+];
+
+pub fn create_strategy(name: &str) -> Option<Box<dyn SolverStrategy>> {
+    use fish::{FishFinder, FishType};
+    use locked_candidate::{LockedCandidateFinder, LockedCandidateType};
+    let strategy: Box<dyn SolverStrategy> = match name {
+        "Full House" => Box::new(full_house::FullHouseFinder::default()),
+        "Naked Single" => Box::new(naked_single::NakedSingleFinder::default()),
+        "Hidden Single" => Box::new(hidden_single::HiddenSingleFinder::default()),
+        "Locked Pair" => Box::new(locked_set::LockedSetFinder::new(2)),
+        "Locked Triple" => Box::new(locked_set::LockedSetFinder::new(3)),
+        "Naked Pair" => Box::new(naked_set::NakedSetFinder::new(2)),
+        "Naked Triple" => Box::new(naked_set::NakedSetFinder::new(3)),
+        "Hidden Pair" => Box::new(hidden_set::HiddenSetFinder::new(2)),
+        "Hidden Triple" => Box::new(hidden_set::HiddenSetFinder::new(3)),
+        "Locked Candidate Pointing" => {
+            Box::new(LockedCandidateFinder::new(LockedCandidateType::Pointing))
+        }
+        "Locked Candidate Claiming" => {
+            Box::new(LockedCandidateFinder::new(LockedCandidateType::Claiming))
+        }
+        "Avoidable Rectangle Type1" => {
+            Box::new(avoidable_rectangle_1::AvoidableRectangleType1Finder::default())
+        }
+        "Avoidable Rectangle Type2" => {
+            Box::new(avoidable_rectangle_2::AvoidableRectangleType2Finder::default())
+        }
+        "Unique Type1" => Box::new(unique_1::Unique1Finder::default()),
+        "Unique Type2" => Box::new(unique_2::Unique2Finder::default()),
+        "Unique Type3" => Box::new(unique_3::Unique3Finder::default()),
+        "Unique Type4" => Box::new(unique_4::Unique4Finder::default()),
+        "Unique Type5" => Box::new(unique_5::Unique5Finder::default()),
+        "Unique Type6" => Box::new(unique_6::Unique6Finder::default()),
+        "Empty Rectangle" => Box::new(empty_rectangle::EmptyRectangleFinder::default()),
+        "Hidden Rectangle" => Box::new(hidden_rectangle::HiddenRectangleFinder::default()),
+        "Bug Plus One" => Box::new(bug_plus_one::BugPlusOneFinder::default()),
+        "Extended Unique Rectangle" => {
+            Box::new(deadly_pattern::extended_rectangle::ExtendedRectangleFinder::default())
+        }
+        "Unique Loop" => Box::new(deadly_pattern::unique_loop::UniqueLoopFinder::default()),
+        "BUG-Lite" => Box::new(deadly_pattern::bug_lite::BugLiteFinder::default()),
+        "Skyscraper" => Box::new(skyscraper::SkyscraperFinder::default()),
+        "Sue de Coq" => Box::new(sue_de_coq::SueDeCoqFinder::default()),
+        "X-Wing" => Box::new(FishFinder::new(FishType::Basic(2)).ok()?),
+        "Remote Pair" => Box::new(chain::remote_pair::RemotePairFinder::default()),
+        "Two String Kit" => Box::new(two_string_kit::TwoStringKitFinder::default()),
+        "Swordfish" => Box::new(FishFinder::new(FishType::Basic(3)).ok()?),
+        "Jellyfish" => Box::new(FishFinder::new(FishType::Basic(4)).ok()?),
+        "XY-Wing" => Box::new(xywing::XYWingFinder::default()),
+        "W-Wing" => Box::new(wwing::WWingFinder::default()),
+        "XYZ-Wing" => Box::new(bent_set::BentSetFinder::new(3)),
+        "WXYZ-Wing" => Box::new(bent_set::BentSetFinder::new(4)),
+        "VWXYZ-Wing" => Box::new(bent_set::BentSetFinder::new(5)),
+        "Finned X-Wing" => Box::new(FishFinder::new(FishType::Finned(2)).ok()?),
+        "Sashimi X-Wing" => Box::new(FishFinder::new(FishType::Sashimi(2)).ok()?),
+        "Finned Swordfish" => Box::new(FishFinder::new(FishType::Finned(3)).ok()?),
+        "Sashimi Swordfish" => Box::new(FishFinder::new(FishType::Sashimi(3)).ok()?),
+        "Finned Jellyfish" => Box::new(FishFinder::new(FishType::Finned(4)).ok()?),
+        "Sashimi Jellyfish" => Box::new(FishFinder::new(FishType::Sashimi(4)).ok()?),
+        "Squirmbag" => Box::new(FishFinder::new(FishType::Basic(5)).ok()?),
+        "Whale" => Box::new(FishFinder::new(FishType::Basic(6)).ok()?),
+        "Leviathan" => Box::new(FishFinder::new(FishType::Basic(7)).ok()?),
+        "Finned Squirmbag" => Box::new(FishFinder::new(FishType::Finned(5)).ok()?),
+        "Sashimi Squirmbag" => Box::new(FishFinder::new(FishType::Sashimi(5)).ok()?),
+        "Finned Whale" => Box::new(FishFinder::new(FishType::Finned(6)).ok()?),
+        "Sashimi Whale" => Box::new(FishFinder::new(FishType::Sashimi(6)).ok()?),
+        "Finned Leviathan" => Box::new(FishFinder::new(FishType::Finned(7)).ok()?),
+        "Sashimi Leviathan" => Box::new(FishFinder::new(FishType::Sashimi(7)).ok()?),
+        "Simple Colors" => Box::new(coloring::simple_colors::SimpleColorsFinder::default()),
+        "Multi Colors" => Box::new(coloring::multi_colors::MultiColorsFinder::default()),
+        "3D Medusa" => Box::new(coloring::medusa::MedusaFinder::default()),
+        "X-Chain" => Box::new(chain::x_chain::XChainFinder::default()),
+        "XY-Chain" => Box::new(chain::xy_chain::XYChainFinder::default()),
+        "Discontinuous Nice Loop" => {
+            Box::new(chain::discontinuous_nice_loop::DiscontinuousNiceLoopFinder::default())
+        }
+        "Continuous Nice Loop" => {
+            Box::new(chain::continuous_nice_loop::ContinuousNiceLoopFinder::default())
+        }
+        "AIC Type1" => Box::new(chain::aic_type1::AicType1Finder::default()),
+        "AIC Type2" => Box::new(chain::aic_type2::AicType2Finder::default()),
+        "ALS-XZ" => Box::new(als::als_xz::AlsXzFinder::default()),
+        "ALS-XY-Wing" => Box::new(als::als_xy_wing::AlsXyWingFinder::default()),
+        "ALS-Chain" => Box::new(als::als_chain::AlsChainFinder::default()),
+        "Death Blossom" => Box::new(als::death_blossom::DeathBlossomFinder::default()),
+        "Aligned Pair Exclusion" => Box::new(aligned_exclusion::AlignedExclusionFinder::new(2)),
+        "Aligned Triple Exclusion" => Box::new(aligned_exclusion::AlignedExclusionFinder::new(3)),
+        "Franken X-Wing" => Box::new(FishFinder::new(FishType::Franken(2)).ok()?),
+        "Franken Swordfish" => Box::new(FishFinder::new(FishType::Franken(3)).ok()?),
+        "Franken Jellyfish" => Box::new(FishFinder::new(FishType::Franken(4)).ok()?),
+        "Mutant X-Wing" => Box::new(FishFinder::new(FishType::Mutant(2)).ok()?),
+        "Mutant Swordfish" => Box::new(FishFinder::new(FishType::Mutant(3)).ok()?),
+        "Franken Squirmbag" => Box::new(FishFinder::new(FishType::Franken(5)).ok()?),
+        "Franken Whale" => Box::new(FishFinder::new(FishType::Franken(6)).ok()?),
+        "Franken Leviathan" => Box::new(FishFinder::new(FishType::Franken(7)).ok()?),
+        "Mutant Jellyfish" => Box::new(FishFinder::new(FishType::Mutant(4)).ok()?),
+        "Mutant Squirmbag" => Box::new(FishFinder::new(FishType::Mutant(5)).ok()?),
+        "Mutant Whale" => Box::new(FishFinder::new(FishType::Mutant(6)).ok()?),
+        "Mutant Leviathan" => Box::new(FishFinder::new(FishType::Mutant(7)).ok()?),
+        "Kraken X-Wing" => Box::new(FishFinder::new(FishType::Kraken(2)).ok()?),
+        "Kraken Swordfish" => Box::new(FishFinder::new(FishType::Kraken(3)).ok()?),
+        "Kraken Jellyfish" => Box::new(FishFinder::new(FishType::Kraken(4)).ok()?),
+        "Kraken Squirmbag" => Box::new(FishFinder::new(FishType::Kraken(5)).ok()?),
+        "Kraken Whale" => Box::new(FishFinder::new(FishType::Kraken(6)).ok()?),
+        "Kraken Leviathan" => Box::new(FishFinder::new(FishType::Kraken(7)).ok()?),
+        "Forcing Chain" => Box::new(chain::forcing_chain::ForcingChainFinder::new(false)),
+        "Forcing Net" => Box::new(chain::forcing_chain::ForcingChainFinder::new(true)),
+        _ => return None,
+    };
+    Some(strategy)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyConfig {
+    pub name: String,
+    pub enabled: bool,
+}
+
+/**
+strategies of the SimpleSolver are tried in the order of the config, the disabled ones are skipped.
+scores are keyed by the step name, like "X-Wing" or "Grouped AIC Type1", they replace the
+difficulty of the step when a solution path is rated.
+
+a profile is a TOML-like text:
+```text
+# comment
+name = "beginner"
+
+[strategies]
+"Full House" = true
+"X-Wing" = false
+
+[scores]
+"Hidden Single" = 14
+```
+the strategies are tried in the order they are listed, the ones not listed are disabled
+**/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolverConfig {
+    pub name: String,
+    pub strategies: Vec<StrategyConfig>,
+    pub scores: BTreeMap<String, u32>,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        let names: Vec<&str> = STRATEGY_NAMES
+            .iter()
+            .copied()
+            .filter(|n| !LARGE_FISH.contains(n))
+            .collect();
+        Self::new_with_order("default", &names)
+    }
+}
+
+impl SolverConfig {
+    // the strategies are enabled in the order of names, the others are disabled
+    fn new_with_order(name: &str, names: &[&str]) -> Self {
+        let mut strategies: Vec<StrategyConfig> = names
+            .iter()
+            .map(|n| StrategyConfig {
+                name: n.to_string(),
+                enabled: true,
+            })
+            .collect();
+        for n in STRATEGY_NAMES.iter().filter(|n| !names.contains(n)) {
+            strategies.push(StrategyConfig {
+                name: n.to_string(),
+                enabled: false,
+            });
+        }
+        SolverConfig {
+            name: name.to_string(),
+            strategies,
+            scores: BTreeMap::new(),
+        }
+    }
+
+    // singles and subsets only
+    pub fn beginner() -> Self {
+        Self::new_with_order("beginner", &BEGINNER_STRATEGIES)
+    }
+
+    // the default order without the slowest strategies, fast enough to rate many puzzles
+    pub fn rating() -> Self {
+        let names: Vec<&str> = STRATEGY_NAMES
+            .iter()
+            .copied()
+            .filter(|n| !SLOW_STRATEGIES.contains(n) && !LARGE_FISH.contains(n))
+            .collect();
+        Self::new_with_order("rating", &names)
+    }
+
+    // the order and scores of HoDoKu, so the scores can be compared
+    pub fn hodoku() -> Self {
+        let mut config = Self::new_with_order("hodoku", &HODOKU_STRATEGIES);
+        for (name, score) in HODOKU_SCORES.iter() {
+            config.scores.insert(name.to_string(), *score);
+        }
+        for (names, score) in HODOKU_GROUP_SCORES.iter() {
+            for name in names.iter() {
+                config.scores.insert(name.to_string(), *score);
+            }
+        }
+        config
+    }
+
+    fn position(&self, name: &str) -> Result<usize> {
+        self.strategies
+            .iter()
+            .position(|s| s.name == name)
+            .ok_or_else(|| SudokuError::InvalidInput(format!("unknown strategy `{}`", name)))
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.strategies.iter().any(|s| s.name == name && s.enabled)
+    }
+
+    pub fn enable(&mut self, name: &str) -> Result<()> {
+        let index = self.position(name)?;
+        self.strategies[index].enabled = true;
+        Ok(())
+    }
+
+    pub fn disable(&mut self, name: &str) -> Result<()> {
+        let index = self.position(name)?;
+        self.strategies[index].enabled = false;
+        Ok(())
+    }
+
+    // the strategy is tried before the other one
+    pub fn move_before(&mut self, name: &str, other: &str) -> Result<()> {
+        let strategy = self.strategies.remove(self.position(name)?);
+        let index = match self.position(other) {
+            Ok(index) => index,
+            Err(e) => {
+                self.strategies.push(strategy);
+                return Err(e);
+            }
+        };
+        self.strategies.insert(index, strategy);
+        Ok(())
+    }
+
+    // the names are moved to the front in the order given, the others keep their order
+    pub fn set_order(&mut self, names: &[&str]) -> Result<()> {
+        for name in names.iter() {
+            self.position(name)?;
+        }
+        for name in names.iter().rev() {
+            let strategy = self.strategies.remove(self.position(name)?);
+            self.strategies.insert(0, strategy);
+        }
+        Ok(())
+    }
+
+    // the score replaces the difficulty of the steps with the name
+    pub fn set_score(&mut self, step_name: &str, score: u32) {
+        self.scores.insert(step_name.to_string(), score);
+    }
+
+    pub fn score(&self, step_name: &str) -> Option<u32> {
+        self.scores.get(step_name).copied()
+    }
+
+    // the enabled strategies in order
+    pub fn create_strategies(&self) -> Result<Vec<Box<dyn SolverStrategy>>> {
+        let mut strategies = Vec::new();
+        for s in self.strategies.iter().filter(|s| s.enabled) {
+            let strategy = create_strategy(&s.name).ok_or_else(|| {
+                SudokuError::InvalidInput(format!("unknown strategy `{}`", s.name))
+            })?;
+            strategies.push(strategy);
+        }
+        Ok(strategies)
+    }
+
+    pub fn from_profile(text: &str) -> Result<Self> {
+        let mut name = "custom".to_string();
+        let mut order: Vec<(String, bool)> = Vec::new();
+        let mut scores = BTreeMap::new();
+        let mut section = "";
+        for (i, line) in text.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = |reason: &str| {
+                SudokuError::InvalidInput(format!("profile line {}: {}", i + 1, reason))
+            };
+            if line.starts_with('[') {
+                section = match line {
+                    "[strategies]" => "strategies",
+                    "[scores]" => "scores",
+                    _ => return Err(error(&format!("unknown section {}", line))),
+                };
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(error("expect key = value"));
+            };
+            let key = unquote(key.trim()).ok_or_else(|| error("unclosed quote in key"))?;
+            let value = value.trim();
+            match section {
+                "strategies" => {
+                    if create_strategy(key).is_none() {
+                        return Err(error(&format!("unknown strategy `{}`", key)));
+                    }
+                    if order.iter().any(|(n, _)| n == key) {
+                        return Err(error(&format!("strategy `{}` is listed twice", key)));
+                    }
+                    let enabled = value
+                        .parse::<bool>()
+                        .map_err(|_| error(&format!("expect true or false, got {}", value)))?;
+                    order.push((key.to_string(), enabled));
+                }
+                "scores" => {
+                    let score = value
+                        .parse::<u32>()
+                        .map_err(|_| error(&format!("expect a score, got {}", value)))?;
+                    scores.insert(key.to_string(), score);
+                }
+                _ => {
+                    if key != "name" {
+                        return Err(error(&format!("unknown key `{}`", key)));
+                    }
+                    name = unquote(value)
+                        .ok_or_else(|| error("unclosed quote in name"))?
+                        .to_string();
+                }
+            }
+        }
+        let mut config = if order.is_empty() {
+            SolverConfig::default()
+        } else {
+            let names: Vec<&str> = order.iter().map(|(n, _)| n.as_str()).collect();
+            let mut config = Self::new_with_order("", &names);
+            for (n, _) in order.iter().filter(|(_, enabled)| !enabled) {
+                config.disable(n)?;
+            }
+            config
+        };
+        config.name = name;
+        config.scores = scores;
+        Ok(config)
+    }
+
+    pub fn to_profile(&self) -> String {
+        let mut res = format!("name = \"{}\"\n\n[strategies]\n", self.name);
+        for s in self.strategies.iter() {
+            res.push_str(&format!("\"{}\" = {}\n", s.name, s.enabled));
+        }
+        if !self.scores.is_empty() {
+            res.push_str("\n[scores]\n");
+            for (name, score) in self.scores.iter() {
+                res.push_str(&format!("\"{}\" = {}\n", name, score));
+            }
+        }
+        res
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+// keys and strings can be quoted or bare
+fn unquote(s: &str) -> Option<&str> {
+    match s.strip_prefix('"') {
+        Some(rest) => rest.strip_suffix('"'),
+        None => Some(s),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        grid::Grid,
+        solver::{
+            SimpleSolver,
+            config::{STRATEGY_NAMES, SolverConfig, create_strategy},
+        },
+    };
+
+    #[test]
+    fn test_solver_config() {
+        assert!(STRATEGY_NAMES.iter().all(|n| create_strategy(n).is_some()));
+        let hodoku = SolverConfig::hodoku();
+        assert_eq!(hodoku.strategies.len(), STRATEGY_NAMES.len());
+        assert!(!hodoku.is_enabled("WXYZ-Wing"));
+        assert!(!SolverConfig::default().is_enabled("Mutant Jellyfish"));
+        assert_eq!(hodoku.score("Finned Franken Whale"), Some(470));
+        let rating = SolverConfig::rating();
+        assert!(rating.is_enabled("Forcing Chain"));
+        assert!(!rating.is_enabled("Forcing Net"));
+        assert_eq!(hodoku.score("Hidden Pair"), Some(70));
+        assert_eq!(
+            SolverConfig::from_profile(&hodoku.to_profile()).unwrap(),
+            hodoku
+        );
+
+        let profile = r#"
+        # singles only
+        name = "singles"
+        [strategies]
+        "Hidden Single" = true   # before naked single
+        "Naked Single" = true
+        "Full House" = false
+        [scores]
+        "Hidden Single" = 10
+        "#;
+        let config = SolverConfig::from_profile(profile).unwrap();
+        assert_eq!(config.name, "singles");
+        assert_eq!(config.strategies[0].name, "Hidden Single");
+        assert!(!config.is_enabled("Full House"));
+        assert!(!config.is_enabled("X-Wing"));
+        assert_eq!(config.create_strategies().unwrap().len(), 2);
+        assert!(SolverConfig::from_profile("[strategies]\n\"Y-Wing\" = true").is_err());
+        assert!(SolverConfig::from_profile("[scores]\nX-Wing = easy").is_err());
+
+        let mut config = SolverConfig::default();
+        config.disable("X-Wing").unwrap();
+        config.move_before("Hidden Single", "Full House").unwrap();
+        assert_eq!(config.strategies[0].name, "Hidden Single");
+        assert!(config.enable("Y-Wing").is_err());
+
+        // needs an X-Wing
+        let s = "1.....569492.561.8.561.924...964.8.1.64.1....218.356.4.4.5...169.5.614.2621.....5";
+        let grid = Grid::new_from_singline_digit(s).unwrap();
+        let beginner = SimpleSolver::from_config(&SolverConfig::beginner()).unwrap();
+        let mut beginner_grid = grid.clone();
+        beginner.solve(&mut beginner_grid);
+        assert!(!beginner_grid.is_solved());
+        let mut config = SolverConfig::hodoku();
+        config.set_score("X-Wing", 1000);
+        let solver = SimpleSolver::from_config(&config).unwrap();
+        let solution = solver.solve(&mut grid.clone());
+        assert!(solution.steps().iter().any(|step| step.name() == "X-Wing"));
+        assert!(solution.score() >= 1000);
+    }
+}
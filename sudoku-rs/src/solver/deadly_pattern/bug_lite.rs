@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    grid_constant::{block, col, get_house_cell_set, row},
+    solver::{
+        SolverStrategy,
+        deadly_pattern::{DeadlyPattern, DeadlyPatternStep, DeadlyPatternType, may_eliminate},
+        step::Step,
+        step_accumulator::StepAccumulator,
+    },
+    util::{create_permutations, digitset::DigitSet},
+};
+
+const MAX_CELLS: usize = 12;
+
+/**
+BUG-Lite: a small BUG, cells with two of three digits each, every digit of the pattern is in its
+rows, columns and blocks twice or not at all. two digits only are unique rectangles and loops
+**/
+#[derive(Default)]
+pub struct BugLiteFinder {}
+
+struct BugSearch {
+    digits: DigitSet,
+    start: u8,
+    pattern: Vec<Candidate>,
+    // the count of pattern candidates of every house and digit
+    counts: [[u8; 10]; 27],
+    // the other candidates of the pattern cells
+    guardians: Vec<Candidate>,
+    found: HashSet<Vec<Candidate>>,
+}
+
+impl BugSearch {
+    fn add(&mut self, grid: &Grid, cell: u8, values: [u8; 2]) {
+        for v in grid.get_cell_candidate(cell).iter() {
+            if !values.contains(&v) {
+                self.guardians.push(Candidate::new(cell, v));
+            }
+        }
+        for v in values {
+            self.pattern.push(Candidate::new(cell, v));
+            for h in [row(cell), col(cell), block(cell)] {
+                self.counts[h as usize][v as usize] += 1;
+            }
+        }
+    }
+
+    fn remove(&mut self, cell: u8, values: [u8; 2]) {
+        self.guardians.retain(|c| c.cell() != cell);
+        for v in values {
+            self.pattern.pop();
+            for h in [row(cell), col(cell), block(cell)] {
+                self.counts[h as usize][v as usize] -= 1;
+            }
+        }
+    }
+
+    // the values fit when no house gets a digit three times
+    fn fits(&self, cell: u8, values: [u8; 2]) -> bool {
+        !self.pattern.iter().any(|c| c.cell() == cell)
+            && values.iter().all(|v| {
+                [row(cell), col(cell), block(cell)]
+                    .iter()
+                    .all(|h| self.counts[*h as usize][*v as usize] < 2)
+            })
+    }
+
+    // a house and digit with only one pattern candidate
+    fn open(&self) -> Option<(u8, u8)> {
+        for house in 0..27 {
+            for v in self.digits.iter() {
+                if self.counts[house as usize][v as usize] == 1 {
+                    return Some((house, v));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl BugLiteFinder {
+    // the pairs of pattern digits of the cell
+    fn pairs(&self, grid: &Grid, cell: u8, digits: &DigitSet) -> Vec<[u8; 2]> {
+        let values = grid.get_cell_candidate(cell).intersect(digits).values();
+        if grid.get_value(cell) != 0 || values.len() < 2 {
+            return Vec::new();
+        }
+        create_permutations(values, 2)
+            .into_iter()
+            .map(|p| [p[0], p[1]])
+            .collect()
+    }
+
+    // true when the accumulator is full
+    fn extend(&self, grid: &Grid, search: &mut BugSearch, acc: &mut dyn StepAccumulator) -> bool {
+        let Some((house, v)) = search.open() else {
+            return self.check_pattern(grid, search, acc);
+        };
+        if search.pattern.len() == MAX_CELLS * 2 {
+            return false;
+        }
+        for cell in get_house_cell_set(house).iter() {
+            if cell <= search.start || !grid.cell_has_candidate(cell, v) {
+                continue;
+            }
+            for pair in self.pairs(grid, cell, &search.digits) {
+                if !pair.contains(&v) || !search.fits(cell, pair) {
+                    continue;
+                }
+                search.add(grid, cell, pair);
+                let full = may_eliminate(&search.guardians) && self.extend(grid, search, acc);
+                search.remove(cell, pair);
+                if full {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn check_pattern(
+        &self,
+        grid: &Grid,
+        search: &mut BugSearch,
+        acc: &mut dyn StepAccumulator,
+    ) -> bool {
+        let pattern = DeadlyPattern::new(search.pattern.clone());
+        if pattern.values() != search.digits || !search.found.insert(pattern.candidates.clone()) {
+            return false;
+        }
+        match DeadlyPatternStep::new(grid, DeadlyPatternType::BugLite, &pattern) {
+            Some(step) => acc.add_step(Step::DeadlyPattern(step)),
+            None => false,
+        }
+    }
+
+    pub fn find_bug_lite(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        for digits in create_permutations((1..=9).collect(), 3) {
+            let mut search = BugSearch {
+                digits: DigitSet::new_from_values(&digits),
+                start: 0,
+                pattern: Vec::new(),
+                counts: [[0; 10]; 27],
+                guardians: Vec::new(),
+                found: HashSet::new(),
+            };
+            for start in 0..81 {
+                search.start = start;
+                for pair in self.pairs(grid, start, &search.digits) {
+                    search.add(grid, start, pair);
+                    let full =
+                        may_eliminate(&search.guardians) && self.extend(grid, &mut search, acc);
+                    search.remove(start, pair);
+                    if full {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl SolverStrategy for BugLiteFinder {
+    fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        self.find_bug_lite(grid, acc);
+    }
+
+    fn name(&self) -> &str {
+        "BugLiteFinder"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        candidate::Candidate,
+        grid::Grid,
+        solver::{
+            SolverStrategy, bitboard::BitboardSolver, deadly_pattern::bug_lite::BugLiteFinder,
+            step::Step, step_accumulator::AllStepAccumulator,
+        },
+    };
+
+    #[test]
+    fn test_bug_lite() {
+        let s = ".---------------.---------------------.-----------------.
+| 29  29     1  | 4      2378   379   | 6    289   5    |
+| 3   269    4  | 5      12678  1679  | 278  289   179  |
+| 8   5      67 | 12679  12367  13679 | 4    29    1379 |
+:---------------+---------------------+-----------------:
+| 5   13689  36 | 167    1367   2     | 78   4     79   |
+| 7   4      2  | 8      9      5     | 3    1     6    |
+| 69  13689  36 | 167    1367   13467 | 278  2589  79   |
+:---------------+---------------------+-----------------:
+| 26  26     8  | 1267   12567  167   | 9    3     4    |
+| 4   236    5  | 269    26     69    | 1    7     8    |
+| 1   7      9  | 3      4      8     | 5    6     2    |
+'---------------'---------------------'-----------------'";
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let solution = *BitboardSolver::new().solve(&grid).values();
+        let mut acc = AllStepAccumulator::default();
+        BugLiteFinder::default().find_step(&grid, &mut acc);
+        let steps = acc.get_steps();
+        assert!(!steps.is_empty());
+        for step in steps.iter() {
+            let Step::DeadlyPattern(dp) = step else {
+                panic!("not deadly pattern step");
+            };
+            for cand in dp.remove_candidates.iter() {
+                assert_ne!(solution[cand.cell() as usize], cand.value());
+            }
+        }
+        // r1c1,r1c2 with 2,9, r6c1,r6c2 with 6,9 and r7c1,r7c2 with 2,6, r6c2 is the only cell with guardians
+        assert!(steps.iter().any(|step| match step {
+            Step::DeadlyPattern(dp) =>
+                dp.remove_candidates == vec![Candidate::new(46, 6), Candidate::new(46, 9)],
+            _ => false,
+        }));
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    grid_constant::cell_index,
+    solver::{
+        SolverStrategy,
+        deadly_pattern::{DeadlyPattern, DeadlyPatternStep, DeadlyPatternType},
+        step::Step,
+        step_accumulator::StepAccumulator,
+    },
+    util::{create_permutations, digitset::DigitSet},
+};
+
+/**
+extended unique rectangle: two rows of one band and three columns, or three rows and two columns
+of one stack, whose six cells have three digits. swapping the two rows (or columns) gives another
+solution
+**/
+#[derive(Default)]
+pub struct ExtendedRectangleFinder {}
+
+impl ExtendedRectangleFinder {
+    // the six cells of every 2x3 and 3x2 rectangle
+    fn rectangles(&self) -> Vec<Vec<u8>> {
+        let mut rectangles = Vec::new();
+        let triples = create_permutations((0..9).collect(), 3);
+        for band in 0..3 {
+            for pair in create_permutations((band * 3..band * 3 + 3).collect(), 2) {
+                for triple in triples.iter() {
+                    let mut rows = Vec::new();
+                    let mut cols = Vec::new();
+                    for p in pair.iter() {
+                        rows.push(
+                            triple
+                                .iter()
+                                .map(|t| cell_index(*p, *t + 9))
+                                .collect::<Vec<u8>>(),
+                        );
+                        cols.push(
+                            triple
+                                .iter()
+                                .map(|t| cell_index(*t, *p + 9))
+                                .collect::<Vec<u8>>(),
+                        );
+                    }
+                    rectangles.push(rows.concat());
+                    rectangles.push(cols.concat());
+                }
+            }
+        }
+        rectangles
+    }
+
+    pub fn find_extended_rectangle(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        for cells in self.rectangles() {
+            if cells.iter().any(|c| grid.get_value(*c) != 0) {
+                continue;
+            }
+            let values = cells.iter().fold(DigitSet::new_empty(), |u, c| {
+                u.union(&grid.get_cell_candidate(*c))
+            });
+            for digits in create_permutations(values.values(), 3) {
+                let digits = DigitSet::new_from_values(&digits);
+                if cells
+                    .iter()
+                    .any(|c| grid.get_cell_candidate(*c).intersect(&digits).count() < 2)
+                {
+                    continue;
+                }
+                let candidates: Vec<Candidate> = cells
+                    .iter()
+                    .flat_map(|c| {
+                        grid.get_cell_candidate(*c)
+                            .intersect(&digits)
+                            .iter()
+                            .map(|v| Candidate::new(*c, v))
+                            .collect::<Vec<Candidate>>()
+                    })
+                    .collect();
+                let pattern = DeadlyPattern::new(candidates);
+                let Some(step) =
+                    DeadlyPatternStep::new(grid, DeadlyPatternType::ExtendedRectangle, &pattern)
+                else {
+                    continue;
+                };
+                if acc.add_step(Step::DeadlyPattern(step)) {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl SolverStrategy for ExtendedRectangleFinder {
+    fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        self.find_extended_rectangle(grid, acc);
+    }
+
+    fn name(&self) -> &str {
+        "ExtendedRectangleFinder"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        candidate::Candidate,
+        grid::Grid,
+        solver::{
+            SolverStrategy, bitboard::BitboardSolver,
+            deadly_pattern::extended_rectangle::ExtendedRectangleFinder, step::Step,
+            step_accumulator::AllStepAccumulator,
+        },
+    };
+
+    #[test]
+    fn test_extended_rectangle() {
+        let s = ".---------------.----------.--------------.
+| 7    9    6   | 4   2  3 | 5   1     8  |
+| 8    15   15  | 7   6  9 | 2   3     4  |
+| 24   24   3   | 8   5  1 | 9   6     7  |
+:---------------+----------+--------------:
+| 9    8    47  | 2   3  5 | 1   4     6  |
+| 245  6    245 | 1   8  7 | 3   245   9  |
+| 3    125  125 | 9   4  6 | 7   8     25 |
+:---------------+----------+--------------:
+| 245  3    9   | 56  1  8 | 46  2457  25 |
+| 6    45   8   | 3   7  2 | 4   9     1  |
+| 1    257  257 | 56  9  4 | 8   257   3  |
+'---------------'----------'--------------'";
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let solution = *BitboardSolver::new().solve(&grid).values();
+        let mut acc = AllStepAccumulator::default();
+        ExtendedRectangleFinder::default().find_step(&grid, &mut acc);
+        let steps = acc.get_steps();
+        assert!(!steps.is_empty());
+        for step in steps.iter() {
+            let Step::DeadlyPattern(dp) = step else {
+                panic!("not deadly pattern step");
+            };
+            for cand in dp.remove_candidates.iter() {
+                assert_ne!(solution[cand.cell() as usize], cand.value());
+            }
+        }
+        // r2c2,r2c3,r6c2,r6c3,r9c2,r9c3 with 1,2,5, the guardians are 7 in r9c2 and r9c3
+        assert!(steps.iter().any(|step| match step {
+            Step::DeadlyPattern(dp) =>
+                dp.guardians == vec![Candidate::new(73, 7), Candidate::new(74, 7)]
+                    && dp.remove_candidates == vec![Candidate::new(79, 7)],
+            _ => false,
+        }));
+    }
+}
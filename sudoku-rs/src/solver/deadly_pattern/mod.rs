@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    grid_constant::{block, col, get_common_buddies, row},
+    util::{
+        digitset::DigitSet,
+        format_step::{format_candidates_cells, format_candidates_values},
+        indexset::IndexSet,
+    },
+};
+
+pub mod bug_lite;
+pub mod extended_rectangle;
+pub mod unique_loop;
+
+/**
+cells with some of their candidates, the pattern digits. the pattern is deadly when every way to
+fill its cells with the pattern digits has another way which puts the same digits in every house:
+the other cells don't see the difference, so the puzzle would have two solutions. a unique
+puzzle needs at least one guardian, a candidate of the cells which is not a pattern digit, to be
+true. the unique rectangle is the smallest deadly pattern
+**/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadlyPattern {
+    pub candidates: Vec<Candidate>,
+}
+
+impl DeadlyPattern {
+    pub fn new(mut candidates: Vec<Candidate>) -> Self {
+        candidates.sort_by_key(|c| (c.cell(), c.value()));
+        candidates.dedup();
+        DeadlyPattern { candidates }
+    }
+
+    pub fn cells(&self) -> IndexSet {
+        IndexSet::new_from_values(self.candidates.iter().map(|c| c.cell()))
+    }
+
+    pub fn values(&self) -> DigitSet {
+        self.candidates
+            .iter()
+            .fold(DigitSet::new_empty(), |mut u, c| {
+                u.add(c.value());
+                u
+            })
+    }
+
+    fn cell_values(&self, cell: u8) -> DigitSet {
+        self.candidates.iter().filter(|c| c.cell() == cell).fold(
+            DigitSet::new_empty(),
+            |mut u, c| {
+                u.add(c.value());
+                u
+            },
+        )
+    }
+
+    // the candidates of the cells which are not in the pattern
+    pub fn guardians(&self, grid: &Grid) -> Vec<Candidate> {
+        let mut guardians = Vec::new();
+        for cell in self.cells().iter() {
+            for v in grid
+                .get_cell_candidate(cell)
+                .difference(&self.cell_values(cell))
+                .iter()
+            {
+                guardians.push(Candidate::new(cell, v));
+            }
+        }
+        guardians
+    }
+
+    pub fn is_deadly(&self, grid: &Grid) -> bool {
+        let cells = self.cells().values();
+        if cells.iter().any(|c| grid.get_value(*c) != 0) {
+            return false;
+        }
+        let options: Vec<Vec<u8>> = cells
+            .iter()
+            .map(|c| self.cell_values(*c).values())
+            .collect();
+        // every filling of the cells is counted by the digits it puts in each house
+        let mut fillings: HashMap<Vec<(u8, DigitSet)>, usize> = HashMap::new();
+        let mut values = vec![0; cells.len()];
+        fill(&cells, &options, &mut values, 0, &mut fillings);
+        !fillings.is_empty() && fillings.values().all(|count| *count >= 2)
+    }
+
+    /**
+    one guardian is true: when all the guardians are in one cell the pattern digits are removed
+    from it, when they all have the same digit it is removed from the cells which see all of them
+    **/
+    pub fn find_eliminations(&self, grid: &Grid, guardians: &[Candidate]) -> Vec<Candidate> {
+        let mut remove_candidates = Vec::new();
+        if guardians.is_empty() {
+            return remove_candidates;
+        }
+        let guardian_cells = IndexSet::new_from_values(guardians.iter().map(|c| c.cell()));
+        if guardian_cells.count() == 1 {
+            let cell = guardians[0].cell();
+            for v in self.cell_values(cell).iter() {
+                remove_candidates.push(Candidate::new(cell, v));
+            }
+        }
+        let value = guardians[0].value();
+        if guardians.iter().all(|c| c.value() == value) {
+            for cell in get_common_buddies(&guardian_cells).iter() {
+                if grid.cell_has_candidate(cell, value) {
+                    remove_candidates.push(Candidate::new(cell, value));
+                }
+            }
+        }
+        remove_candidates
+    }
+}
+
+// the guardians are all in one cell or all have the same digit, more guardians can't change it
+// back, so a search can stop when it is false
+pub fn may_eliminate(guardians: &[Candidate]) -> bool {
+    guardians.iter().all(|c| c.cell() == guardians[0].cell())
+        || guardians.iter().all(|c| c.value() == guardians[0].value())
+}
+
+// try every value of the cells which is not already in one of its houses
+fn fill(
+    cells: &[u8],
+    options: &[Vec<u8>],
+    values: &mut Vec<u8>,
+    index: usize,
+    fillings: &mut HashMap<Vec<(u8, DigitSet)>, usize>,
+) {
+    if index == cells.len() {
+        let mut houses: HashMap<u8, DigitSet> = HashMap::new();
+        for (cell, v) in cells.iter().zip(values.iter()) {
+            for house in [row(*cell), col(*cell), block(*cell)] {
+                houses.entry(house).or_insert(DigitSet::new_empty()).add(*v);
+            }
+        }
+        let mut key: Vec<(u8, DigitSet)> = houses.into_iter().collect();
+        key.sort_by_key(|(house, _)| *house);
+        *fillings.entry(key).or_insert(0) += 1;
+        return;
+    }
+    let cell = cells[index];
+    for v in options[index].iter() {
+        let conflict = (0..index).any(|i| {
+            values[i] == *v
+                && (row(cells[i]) == row(cell)
+                    || col(cells[i]) == col(cell)
+                    || block(cells[i]) == block(cell))
+        });
+        if conflict {
+            continue;
+        }
+        values[index] = *v;
+        fill(cells, options, values, index + 1, fillings);
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeadlyPatternType {
+    UniqueLoop,
+    ExtendedRectangle,
+    BugLite,
+}
+
+#[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeadlyPatternStep {
+    pub pattern_type: DeadlyPatternType,
+    pub pattern: Vec<Candidate>,
+    pub guardians: Vec<Candidate>,
+    pub remove_candidates: Vec<Candidate>,
+}
+
+impl DeadlyPatternStep {
+    // None when the guardians allow no elimination
+    pub fn new(
+        grid: &Grid,
+        pattern_type: DeadlyPatternType,
+        pattern: &DeadlyPattern,
+    ) -> Option<Self> {
+        let guardians = pattern.guardians(grid);
+        let remove_candidates = pattern.find_eliminations(grid, &guardians);
+        if remove_candidates.is_empty() || !pattern.is_deadly(grid) {
+            return None;
+        }
+        Some(DeadlyPatternStep {
+            pattern_type,
+            pattern: pattern.candidates.clone(),
+            guardians,
+            remove_candidates,
+        })
+    }
+
+    pub fn apply(&self, grid: &mut Grid) {
+        for cand in self.remove_candidates.iter() {
+            grid.remvoe_candidate(cand);
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self.pattern_type {
+            DeadlyPatternType::UniqueLoop => "Unique Loop",
+            DeadlyPatternType::ExtendedRectangle => "Extended Unique Rectangle",
+            DeadlyPatternType::BugLite => "BUG-Lite",
+        }
+    }
+
+    pub fn difficulty(&self) -> u32 {
+        match self.pattern_type {
+            DeadlyPatternType::ExtendedRectangle => 110,
+            DeadlyPatternType::UniqueLoop => 120,
+            DeadlyPatternType::BugLite => 130,
+        }
+    }
+
+    pub fn explain(&self) -> String {
+        format!(
+            "<h3>{}</h3><p>cells {} with {} would give two solutions, one of the guardians {} must be true, {} can be removed from {}</p>",
+            self.name(),
+            format_candidates_cells(&self.pattern),
+            format_candidates_values(&self.pattern),
+            format_candidates_cells(&self.guardians),
+            format_candidates_values(&self.remove_candidates),
+            format_candidates_cells(&self.remove_candidates)
+        )
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::HashSet;
+
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    grid_constant::{block, col, row},
+    solver::{
+        SolverStrategy,
+        deadly_pattern::{DeadlyPattern, DeadlyPatternStep, DeadlyPatternType, may_eliminate},
+        step::Step,
+        step_accumulator::StepAccumulator,
+    },
+    util::{create_permutations, indexset::IndexSet},
+};
+
+// longer loops are rare and slow to find
+const MAX_LENGTH: usize = 14;
+
+/**
+unique loop: six or more cells with the digits a and b, every row, column and block of the loop
+has two of its cells. the digits alternate along the loop, so they can be swapped
+**/
+#[derive(Default)]
+pub struct UniqueLoopFinder {}
+
+struct LoopSearch {
+    a: u8,
+    b: u8,
+    cells: Vec<u8>,
+    path: Vec<u8>,
+    // the count of path cells in every house
+    house_counts: [u8; 27],
+    // the other candidates of the path cells
+    guardians: Vec<Candidate>,
+    found: HashSet<IndexSet>,
+}
+
+impl LoopSearch {
+    fn push(&mut self, grid: &Grid, cell: u8) {
+        for h in houses(cell) {
+            self.house_counts[h as usize] += 1;
+        }
+        self.path.push(cell);
+        for v in grid.get_cell_candidate(cell).iter() {
+            if v != self.a && v != self.b {
+                self.guardians.push(Candidate::new(cell, v));
+            }
+        }
+    }
+
+    fn pop(&mut self) {
+        let cell = self.path.pop().unwrap();
+        for h in houses(cell) {
+            self.house_counts[h as usize] -= 1;
+        }
+        self.guardians.retain(|c| c.cell() != cell);
+    }
+}
+
+fn houses(cell: u8) -> [u8; 3] {
+    [row(cell), col(cell), block(cell)]
+}
+
+fn share_house(a: u8, b: u8) -> bool {
+    row(a) == row(b) || col(a) == col(b) || block(a) == block(b)
+}
+
+impl UniqueLoopFinder {
+    // true when the accumulator is full
+    fn extend(&self, grid: &Grid, search: &mut LoopSearch, acc: &mut dyn StepAccumulator) -> bool {
+        let start = search.path[0];
+        let last = *search.path.last().unwrap();
+        if search.path.len() >= 6
+            && share_house(last, start)
+            && search.house_counts.iter().all(|c| *c == 0 || *c == 2)
+        {
+            let cells = IndexSet::new_from_values(search.path.iter().copied());
+            if search.found.insert(cells) && self.check_loop(grid, search, acc) {
+                return true;
+            }
+        }
+        if search.path.len() == MAX_LENGTH {
+            return false;
+        }
+        for i in 0..search.cells.len() {
+            let next = search.cells[i];
+            if next <= start || search.path.contains(&next) || !share_house(last, next) {
+                continue;
+            }
+            if houses(next)
+                .iter()
+                .any(|h| search.house_counts[*h as usize] == 2)
+            {
+                continue;
+            }
+            search.push(grid, next);
+            let full = may_eliminate(&search.guardians) && self.extend(grid, search, acc);
+            search.pop();
+            if full {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn check_loop(&self, grid: &Grid, search: &LoopSearch, acc: &mut dyn StepAccumulator) -> bool {
+        let candidates = search
+            .path
+            .iter()
+            .flat_map(|c| [Candidate::new(*c, search.a), Candidate::new(*c, search.b)])
+            .collect();
+        let pattern = DeadlyPattern::new(candidates);
+        match DeadlyPatternStep::new(grid, DeadlyPatternType::UniqueLoop, &pattern) {
+            Some(step) => acc.add_step(Step::DeadlyPattern(step)),
+            None => false,
+        }
+    }
+
+    pub fn find_unique_loop(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        for values in create_permutations((1..=9).collect(), 2) {
+            let (a, b) = (values[0], values[1]);
+            let cells: Vec<u8> = (0..81)
+                .filter(|c| grid.cell_has_candidate(*c, a) && grid.cell_has_candidate(*c, b))
+                .collect();
+            if cells.len() < 6 {
+                continue;
+            }
+            let mut search = LoopSearch {
+                a,
+                b,
+                cells: cells.clone(),
+                path: Vec::new(),
+                house_counts: [0; 27],
+                guardians: Vec::new(),
+                found: HashSet::new(),
+            };
+            for start in cells {
+                search.push(grid, start);
+                let full = self.extend(grid, &mut search, acc);
+                search.pop();
+                if full {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl SolverStrategy for UniqueLoopFinder {
+    fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        self.find_unique_loop(grid, acc);
+    }
+
+    fn name(&self) -> &str {
+        "UniqueLoopFinder"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        candidate::Candidate,
+        grid::Grid,
+        solver::{
+            SolverStrategy, bitboard::BitboardSolver,
+            deadly_pattern::unique_loop::UniqueLoopFinder, step::Step,
+            step_accumulator::AllStepAccumulator,
+        },
+    };
+
+    #[test]
+    fn test_unique_loop() {
+        let s = ".----------.----------.-------------------.
+| 8  2  3  | 1   5  6 | 47      47    9   |
+| 4  9  56 | 2   3  7 | 256     8     1   |
+| 7  1  56 | 24  9  8 | 234567  4567  256 |
+:----------+----------+-------------------:
+| 1  5  9  | 7   6  3 | 8       2     4   |
+| 6  3  8  | 5   4  2 | 1       9     7   |
+| 2  4  7  | 8   1  9 | 56      3     56  |
+:----------+----------+-------------------:
+| 3  7  1  | 9   2  5 | 46      46    8   |
+| 5  8  2  | 6   7  4 | 9       1     3   |
+| 9  6  4  | 3   8  1 | 257     57    25  |
+'----------'----------'-------------------'";
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let solution = *BitboardSolver::new().solve(&grid).values();
+        let mut acc = AllStepAccumulator::default();
+        UniqueLoopFinder::default().find_step(&grid, &mut acc);
+        let steps = acc.get_steps();
+        assert!(!steps.is_empty());
+        for step in steps.iter() {
+            let Step::DeadlyPattern(dp) = step else {
+                panic!("not deadly pattern step");
+            };
+            for cand in dp.remove_candidates.iter() {
+                assert_ne!(solution[cand.cell() as usize], cand.value());
+            }
+        }
+        // r2c3,r2c7,r6c7,r6c9,r3c9,r3c3 with 5,6, the guardians are 2 in r2c7 and r3c9
+        assert!(steps.iter().any(|step| match step {
+            Step::DeadlyPattern(dp) =>
+                dp.pattern.len() == 12 && dp.remove_candidates == vec![Candidate::new(24, 2)],
+            _ => false,
+        }));
+    }
+}
@@ -74,6 +74,7 @@ fn init_empty_rectangle_pattern() -> EmptyPattern {
 }
 
 #[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EmptyRectangle {
     pub value: u8,
     pub remove_candidates: Vec<Candidate>,
@@ -1,72 +1,158 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::{
     candidate::Candidate,
+    error::{Result, SudokuError},
     grid::{Grid, HouseType},
-    grid_constant::{block, col, row},
-    solver::{SolverStrategy, step::Step, step_accumulator::StepAccumulator},
-    util::{create_permutations, indexset::IndexSet},
+    grid_constant::{block, col, get_cell_buddies, get_common_buddies, row},
+    solver::{
+        SolverStrategy,
+        chain::{
+            forcing_chain::{ForcingChainFinder, Propagation},
+            graph::Graph,
+            link::Chain,
+        },
+        step::Step,
+        step_accumulator::StepAccumulator,
+    },
+    util::{
+        create_permutations,
+        format_step::{format_candidates_cells, format_house},
+        indexset::IndexSet,
+    },
 };
 
+// fish names by kind and size, the size is from 2 to 7
+static FISH_NAMES: [[&str; 6]; 8] = [
+    [
+        "X-Wing",
+        "Swordfish",
+        "Jellyfish",
+        "Squirmbag",
+        "Whale",
+        "Leviathan",
+    ],
+    [
+        "Finned X-Wing",
+        "Finned Swordfish",
+        "Finned Jellyfish",
+        "Finned Squirmbag",
+        "Finned Whale",
+        "Finned Leviathan",
+    ],
+    [
+        "Sashimi X-Wing",
+        "Sashimi Swordfish",
+        "Sashimi Jellyfish",
+        "Sashimi Squirmbag",
+        "Sashimi Whale",
+        "Sashimi Leviathan",
+    ],
+    [
+        "Franken X-Wing",
+        "Franken Swordfish",
+        "Franken Jellyfish",
+        "Franken Squirmbag",
+        "Franken Whale",
+        "Franken Leviathan",
+    ],
+    [
+        "Finned Franken X-Wing",
+        "Finned Franken Swordfish",
+        "Finned Franken Jellyfish",
+        "Finned Franken Squirmbag",
+        "Finned Franken Whale",
+        "Finned Franken Leviathan",
+    ],
+    [
+        "Mutant X-Wing",
+        "Mutant Swordfish",
+        "Mutant Jellyfish",
+        "Mutant Squirmbag",
+        "Mutant Whale",
+        "Mutant Leviathan",
+    ],
+    [
+        "Finned Mutant X-Wing",
+        "Finned Mutant Swordfish",
+        "Finned Mutant Jellyfish",
+        "Finned Mutant Squirmbag",
+        "Finned Mutant Whale",
+        "Finned Mutant Leviathan",
+    ],
+    [
+        "Kraken X-Wing",
+        "Kraken Swordfish",
+        "Kraken Jellyfish",
+        "Kraken Squirmbag",
+        "Kraken Whale",
+        "Kraken Leviathan",
+    ],
+];
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FishType {
-    XWing,
-    SwordFish,
-    JellyFish,
-    FinnedXWing,
-    FinnedSowrdFish,
-    FinnedJellFish,
-    SashimiXWing,
-    SashimiSwordFish,
-    SashimiJellyFish,
+    Basic(u8),
+    Finned(u8),
+    Sashimi(u8),
+    // blocks can be used in the base or the cover sets
+    Franken(u8),
+    // any house can be used in the base or the cover sets
+    Mutant(u8),
+    // finned fish whose eliminations are proved by chains from every fin
+    Kraken(u8),
 }
 impl FishType {
     pub fn degree(&self) -> u8 {
         match self {
-            FishType::XWing | FishType::FinnedXWing | FishType::SashimiXWing => 2,
-            FishType::SwordFish | FishType::FinnedSowrdFish | FishType::SashimiSwordFish => 3,
-            FishType::JellyFish | FishType::FinnedJellFish | FishType::SashimiJellyFish => 4,
+            FishType::Basic(size)
+            | FishType::Finned(size)
+            | FishType::Sashimi(size)
+            | FishType::Franken(size)
+            | FishType::Mutant(size)
+            | FishType::Kraken(size) => *size,
         }
     }
     pub fn finned(&self) -> bool {
-        match self {
-            FishType::FinnedXWing | FishType::FinnedSowrdFish | FishType::FinnedJellFish => true,
-            _ => false,
-        }
+        matches!(self, FishType::Finned(_))
     }
     pub fn sashimi(&self) -> bool {
-        match self {
-            FishType::SashimiXWing | FishType::SashimiSwordFish | FishType::SashimiJellyFish => {
-                true
-            }
-            _ => false,
-        }
+        matches!(self, FishType::Sashimi(_))
     }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Fish {
     pub remove_candidates: Vec<Candidate>,
     pub highlight_candidates: Vec<Candidate>,
     pub fins: Vec<Candidate>,
+    // candidates in more than one base house
+    pub endo_fins: Vec<Candidate>,
     pub basics: Vec<u8>,
     pub covers: Vec<u8>,
     pub value: u8,
     pub fish_type: FishType,
+    // kraken fish: chains from the fins to the eliminations
+    pub chains: Vec<Chain>,
 }
 
 impl Fish {
     pub fn difficulty(&self) -> u32 {
+        let size = self.fish_type.degree() as u32;
+        let finned = !self.fins.is_empty() || !self.endo_fins.is_empty();
         match self.fish_type {
-            FishType::XWing => 140,
-            FishType::SwordFish => 150,
-            FishType::JellyFish => 160,
-            FishType::FinnedXWing => 150,
-            FishType::FinnedSowrdFish => 200,
-            FishType::FinnedJellFish => 250,
-            FishType::SashimiXWing => 150,
-            FishType::SashimiSwordFish => 240,
-            FishType::SashimiJellyFish => 260,
+            FishType::Basic(_) => 120 + size * 10,
+            FishType::Finned(2) | FishType::Sashimi(2) => 150,
+            FishType::Finned(_) => 50 * size + 50,
+            FishType::Sashimi(3) => 240,
+            FishType::Sashimi(_) => 20 * size + 180,
+            FishType::Franken(_) if finned => 330 + size * 10,
+            FishType::Franken(_) => 300 + size * 10,
+            FishType::Mutant(_) if finned => 430 + size * 10,
+            FishType::Mutant(_) => 400 + size * 10,
+            FishType::Kraken(_) => 450 + size * 10,
         }
     }
 
@@ -75,31 +161,73 @@ impl Fish {
             grid.remvoe_candidate(cand);
         }
     }
+
     pub fn name(&self) -> &str {
-        match self.fish_type {
-            FishType::XWing => "XWing",
-            FishType::SwordFish => "Swordfish",
-            FishType::JellyFish => "Jellyfish",
-            FishType::FinnedXWing => "Finned XWing",
-            FishType::FinnedSowrdFish => "Finned Sowrdfish",
-            FishType::FinnedJellFish => "Finned Jellfish",
-            FishType::SashimiXWing => "Sashimi XWing",
-            FishType::SashimiSwordFish => "Sashimi Swordfish",
-            FishType::SashimiJellyFish => "Sashimi Jellyfish",
-        }
+        let finned = !self.fins.is_empty() || !self.endo_fins.is_empty();
+        let kind = match self.fish_type {
+            FishType::Basic(_) => 0,
+            FishType::Finned(_) => 1,
+            FishType::Sashimi(_) => 2,
+            FishType::Franken(_) if finned => 4,
+            FishType::Franken(_) => 3,
+            FishType::Mutant(_) if finned => 6,
+            FishType::Mutant(_) => 5,
+            FishType::Kraken(_) => 7,
+        };
+        (self.fish_type.degree() as usize)
+            .checked_sub(2)
+            .and_then(|i| FISH_NAMES[kind].get(i))
+            .copied()
+            .unwrap_or("")
     }
+
     pub fn explain(&self) -> String {
-        format!("<h3>{}</h3>", self.name())
+        let basics: Vec<String> = self.basics.iter().map(|h| format_house(*h)).collect();
+        let covers: Vec<String> = self.covers.iter().map(|h| format_house(*h)).collect();
+        format!(
+            "<h3>{}</h3><p>base sets {}, cover sets {}, {} can be removed from {}</p>",
+            self.name(),
+            basics.join(","),
+            covers.join(","),
+            self.value,
+            format_candidates_cells(&self.remove_candidates)
+        )
     }
 }
 
+// limits of the fins like hodoku, more fins rarely lead to an elimination
+const MAX_FINS: usize = 5;
+const MAX_ENDO_FINS: usize = 2;
+
+// the state of searching franken, mutant and kraken fish for one value
+struct ComplexFishSearch<'a> {
+    grid: &'a Grid,
+    graph: Option<&'a Graph>,
+    value: u8,
+    house_cells: [IndexSet; 27],
+    basics: Vec<u8>,
+    basic_cells: IndexSet,
+    endo_fins: IndexSet,
+    covers: Vec<u8>,
+    // the most base candidates a cover set can hold
+    max_cover: usize,
+    propagations: HashMap<Candidate, Propagation>,
+}
+
 pub struct FishFinder {
     fish_type: FishType,
 }
 
 impl FishFinder {
-    pub fn new(fish_type: FishType) -> Self {
-        Self { fish_type }
+    pub fn new(fish_type: FishType) -> Result<Self> {
+        let size = fish_type.degree();
+        if !(2..=7).contains(&size) {
+            return Err(SudokuError::InvalidInput(format!(
+                "fish size {} is not in 2..=7",
+                size
+            )));
+        }
+        Ok(Self { fish_type })
     }
     pub fn find_fish(
         &self,
@@ -128,13 +256,24 @@ impl FishFinder {
                 .collect();
 
             let basic_permutation = create_permutations(basics, self.fish_type.degree());
-            let covers_permutation = create_permutations(covers, self.fish_type.degree());
 
             for basic_permu in basic_permutation {
                 let basic_cells = basic_permu
                     .iter()
                     .map(|h| grid.pential_cells_in_house(*h, value))
                     .fold(IndexSet::new_empty(), |u, s| u.union(&s));
+                // every cover set must hold base candidates
+                let basic_covers: Vec<u8> = covers
+                    .iter()
+                    .copied()
+                    .filter(|h| {
+                        !grid
+                            .pential_cells_in_house(*h, value)
+                            .intersect(&basic_cells)
+                            .is_empty()
+                    })
+                    .collect();
+                let covers_permutation = create_permutations(basic_covers, self.fish_type.degree());
 
                 for cover_permu in covers_permutation.iter() {
                     let cover_sets: Vec<IndexSet> = cover_permu
@@ -185,8 +324,10 @@ impl FishFinder {
                             basics: basic_permu.clone(),
                             covers: cover_permu.clone(),
                             fins: Vec::new(),
+                            endo_fins: Vec::new(),
                             value: value,
                             fish_type: self.fish_type.clone(),
+                            chains: Vec::new(),
                         };
                         if acc.add_step(Step::Fish(fish)) {
                             return;
@@ -248,10 +389,12 @@ impl FishFinder {
                             remove_candidates,
                             highlight_candidates,
                             fins: fin_candidates,
+                            endo_fins: Vec::new(),
                             basics: basic_permu.clone(),
                             covers: cover_permu.clone(),
                             value,
                             fish_type: self.fish_type.clone(),
+                            chains: Vec::new(),
                         };
                         if acc.add_step(Step::Fish(fish)) {
                             return;
@@ -288,10 +431,12 @@ impl FishFinder {
                             remove_candidates,
                             highlight_candidates,
                             fins: fin_candidates,
+                            endo_fins: Vec::new(),
                             basics: basic_permu.clone(),
                             covers: cover_permu.clone(),
                             value,
                             fish_type: self.fish_type.clone(),
+                            chains: Vec::new(),
                         };
                         if acc.add_step(Step::Fish(fish)) {
                             return;
@@ -301,10 +446,248 @@ impl FishFinder {
             }
         }
     }
+
+    // the base and the cover houses can be used by the fish type
+    fn house_choices(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let rows = HouseType::Row.houses();
+        let cols = HouseType::Column.houses();
+        let blocks = HouseType::Block.houses();
+        match self.fish_type {
+            FishType::Franken(_) => vec![
+                ([rows, blocks].concat(), [cols, blocks].concat()),
+                ([cols, blocks].concat(), [rows, blocks].concat()),
+            ],
+            FishType::Mutant(_) => {
+                let all: Vec<u8> = (0..27).collect();
+                vec![(all.clone(), all)]
+            }
+            _ => vec![
+                (rows.to_vec(), cols.to_vec()),
+                (cols.to_vec(), rows.to_vec()),
+            ],
+        }
+    }
+
+    // rows with blocks against columns with blocks, or the other way
+    fn is_franken_shape(&self, basics: &[u8], covers: &[u8]) -> bool {
+        let no_cols = |houses: &[u8]| houses.iter().all(|h| !(9..18).contains(h));
+        let no_rows = |houses: &[u8]| houses.iter().all(|h| *h >= 9);
+        (no_cols(basics) && no_rows(covers)) || (no_rows(basics) && no_cols(covers))
+    }
+
+    fn has_block(&self, basics: &[u8], covers: &[u8]) -> bool {
+        basics.iter().chain(covers.iter()).any(|h| *h >= 18)
+    }
+
+    // the target is seen by the fin or is removed by the chain from the fin
+    fn kraken_chains(
+        &self,
+        grid: &Grid,
+        graph: &Graph,
+        fins: &IndexSet,
+        target: Candidate,
+        propagations: &mut HashMap<Candidate, Propagation>,
+    ) -> Option<Vec<Chain>> {
+        let mut chains = Vec::new();
+        for fin in fins.iter() {
+            if get_cell_buddies(fin).contains(target.cell()) {
+                continue;
+            }
+            let fin = Candidate::new(fin, target.value());
+            let propagation = propagations.entry(fin).or_insert_with(|| {
+                ForcingChainFinder::new(false).propagate(grid, graph, fin, true)
+            });
+            if propagation.contradiction.is_some() || !propagation.off.contains_key(&target) {
+                return None;
+            }
+            chains.push(propagation.chain_to(target, false));
+        }
+        Some(chains)
+    }
+
+    // a fish with the chosen base and cover sets, finds its eliminations and add the step
+    fn check_complex_fish(
+        &self,
+        search: &mut ComplexFishSearch,
+        covers: &[u8],
+        cover_cells: IndexSet,
+        acc: &mut dyn StepAccumulator,
+    ) {
+        let skip = match self.fish_type {
+            FishType::Franken(_) => !self.has_block(&search.basics, covers),
+            FishType::Mutant(_) => self.is_franken_shape(&search.basics, covers),
+            _ => false,
+        };
+        if skip {
+            return;
+        }
+        let value = search.value;
+        let fins = search.basic_cells.difference(&cover_cells);
+        let all_fins = fins.union(&search.endo_fins);
+        let targets = cover_cells.difference(&search.basic_cells);
+        if targets.is_empty() || (search.graph.is_some() && fins.is_empty()) {
+            return;
+        }
+        let seen_cells = if all_fins.is_empty() {
+            targets
+        } else {
+            targets.intersect(&get_common_buddies(&all_fins))
+        };
+        let mut chains = Vec::new();
+        let remove_cells = match &search.graph {
+            Some(graph) => {
+                // the targets seen by every fin are left to the finned fish
+                let mut remove_cells = IndexSet::new_empty();
+                for target in targets.difference(&seen_cells).iter() {
+                    if let Some(fin_chains) = self.kraken_chains(
+                        search.grid,
+                        graph,
+                        &fins,
+                        Candidate::new(target, value),
+                        &mut search.propagations,
+                    ) {
+                        remove_cells.add(target);
+                        chains.extend(fin_chains);
+                    }
+                }
+                remove_cells
+            }
+            None => seen_cells,
+        };
+        if remove_cells.is_empty() {
+            return;
+        }
+        let to_candidates = |cells: IndexSet| -> Vec<Candidate> {
+            cells
+                .iter()
+                .map(|cell| Candidate::new(cell, value))
+                .collect()
+        };
+        let fish = Fish {
+            remove_candidates: to_candidates(remove_cells),
+            highlight_candidates: to_candidates(search.basic_cells.difference(&all_fins)),
+            fins: to_candidates(fins),
+            endo_fins: to_candidates(search.endo_fins),
+            basics: search.basics.clone(),
+            covers: covers.to_vec(),
+            value,
+            fish_type: self.fish_type.clone(),
+            chains,
+        };
+        acc.add_step(Step::Fish(fish));
+    }
+
+    // choose the cover sets one by one, stop when too many base candidates are left uncovered
+    fn search_covers(
+        &self,
+        search: &mut ComplexFishSearch,
+        start: usize,
+        chosen: &mut Vec<u8>,
+        cover_cells: IndexSet,
+        acc: &mut dyn StepAccumulator,
+    ) {
+        let left = (self.fish_type.degree() as usize) - chosen.len();
+        if left == 0 {
+            self.check_complex_fish(search, chosen, cover_cells, acc);
+            return;
+        }
+        let uncovered = search.basic_cells.difference(&cover_cells).count() as usize;
+        if uncovered > MAX_FINS + left * search.max_cover {
+            return;
+        }
+        for i in start..search.covers.len() {
+            if search.covers.len() - i < left {
+                break;
+            }
+            let house = search.covers[i];
+            chosen.push(house);
+            let cells = cover_cells.union(&search.house_cells[house as usize]);
+            self.search_covers(search, i + 1, chosen, cells, acc);
+            chosen.pop();
+            if acc.is_finish() {
+                return;
+            }
+        }
+    }
+
+    // general fish: base houses may overlap (endo fins) and blocks can be used.
+    // cannibalistic eliminations are not searched
+    pub fn find_complex_fish(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        let size = self.fish_type.degree();
+        let graph = match self.fish_type {
+            FishType::Kraken(_) => Some(Graph::new_aic_graph(grid)),
+            _ => None,
+        };
+        for value in 1..=9 {
+            let mut house_cells = [IndexSet::new_empty(); 27];
+            for (house, cells) in house_cells.iter_mut().enumerate() {
+                *cells = grid.pential_cells_in_house(house as u8, value);
+            }
+            let mut search = ComplexFishSearch {
+                grid,
+                graph: graph.as_ref(),
+                value,
+                house_cells,
+                basics: Vec::new(),
+                basic_cells: IndexSet::new_empty(),
+                endo_fins: IndexSet::new_empty(),
+                covers: Vec::new(),
+                max_cover: 0,
+                propagations: HashMap::new(),
+            };
+            for (base_houses, cover_houses) in self.house_choices() {
+                let bases: Vec<u8> = base_houses
+                    .into_iter()
+                    .filter(|h| !house_cells[*h as usize].is_empty())
+                    .collect();
+                for basic_permu in create_permutations(bases, size) {
+                    let mut basic_cells = IndexSet::new_empty();
+                    let mut endo_fins = IndexSet::new_empty();
+                    for h in basic_permu.iter() {
+                        let cells = house_cells[*h as usize];
+                        endo_fins = endo_fins.union(&basic_cells.intersect(&cells));
+                        basic_cells = basic_cells.union(&cells);
+                    }
+                    if endo_fins.count() as usize > MAX_ENDO_FINS {
+                        continue;
+                    }
+                    let covers: Vec<u8> = cover_houses
+                        .iter()
+                        .copied()
+                        .filter(|h| {
+                            !basic_permu.contains(h)
+                                && !house_cells[*h as usize].intersect(&basic_cells).is_empty()
+                        })
+                        .collect();
+                    search.max_cover = covers
+                        .iter()
+                        .map(|h| house_cells[*h as usize].intersect(&basic_cells).count() as usize)
+                        .max()
+                        .unwrap_or(0);
+                    search.basics = basic_permu;
+                    search.basic_cells = basic_cells;
+                    search.endo_fins = endo_fins;
+                    search.covers = covers;
+                    let mut chosen = Vec::new();
+                    self.search_covers(&mut search, 0, &mut chosen, IndexSet::new_empty(), acc);
+                    if acc.is_finish() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl SolverStrategy for FishFinder {
     fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        if matches!(
+            self.fish_type,
+            FishType::Franken(_) | FishType::Mutant(_) | FishType::Kraken(_)
+        ) {
+            self.find_complex_fish(grid, acc);
+            return;
+        }
         self.find_fish(HouseType::Row, HouseType::Column, grid, acc);
         if acc.is_finish() {
             return;
@@ -321,6 +704,7 @@ mod test {
         grid::Grid,
         solver::{
             SolverStrategy,
+            brute_force::BruteForceSolver,
             fish::{Fish, FishFinder, FishType},
             step::Step,
             step_accumulator::AllStepAccumulator,
@@ -331,7 +715,7 @@ mod test {
     fn test_finned_xwing() {
         let s = ":0310:9:.+52+6+7.3.+8.3...+5+6+2767..+3+2+5.+1+2+8...61.+5.+6....+2.47+1+452+3+86+9+82+73+149+5+6.9.+2+67+48+3+3+469+58+71+2::933:r24 c35 fr2c1";
         let grid = Grid::new_from_hodoku_line(s).unwrap();
-        let finder = FishFinder::new(FishType::FinnedXWing);
+        let finder = FishFinder::new(FishType::Finned(2)).unwrap();
         let mut acc = AllStepAccumulator::default();
         finder.find_step(&grid, &mut acc);
         let steps = acc.get_steps();
@@ -342,7 +726,7 @@ mod test {
     fn test_sashimi_xwing() {
         let s = ":0320:3:......3+8+99.4..2+561....9.72+4+4619+2+78+53+8+5+93+64+17+2..2...+4+9+6.97.1..4+85....8+9.+7.....+9..+5::371:c36 r37 fr8c3 fr9c3";
         let grid = Grid::new_from_hodoku_line(s).unwrap();
-        let finder = FishFinder::new(FishType::SashimiXWing);
+        let finder = FishFinder::new(FishType::Sashimi(2)).unwrap();
         let mut acc = AllStepAccumulator::default();
         finder.find_step(&grid, &mut acc);
         let steps = acc.get_steps();
@@ -353,7 +737,7 @@ mod test {
     fn test_finned_swordfish() {
         let s = ":0311:7:+2.3.+186+5.41+6+75+39+8+2.+5+8.+26.1.84.3+6+2.9+5+62.+8.543.5+3.1+4.+8+2+6.+6+52...+4+83.+4+58.26..+8+2+6.45+7.::737:c159 r357 fr1c9";
         let grid = Grid::new_from_hodoku_line(s).unwrap();
-        let finder = FishFinder::new(FishType::FinnedSowrdFish);
+        let finder = FishFinder::new(FishType::Finned(3)).unwrap();
         let mut acc = AllStepAccumulator::default();
         finder.find_step(&grid, &mut acc);
         let steps = acc.get_steps();
@@ -372,7 +756,7 @@ mod test {
     fn test_sashimi_swordfish() {
         let s = ":0321:2:2.7+89+5+6.+15..7.+4+9.8.9+8..6......+4.+9......6.+8.938.9.5+3764...+3+62......54+7...+7.3+9+814.6::245 255:r269 c258 fr6c4";
         let grid = Grid::new_from_hodoku_line(s).unwrap();
-        let finder = FishFinder::new(FishType::SashimiSwordFish);
+        let finder = FishFinder::new(FishType::Sashimi(3)).unwrap();
         let mut acc = AllStepAccumulator::default();
         finder.find_step(&grid, &mut acc);
         let steps = acc.get_steps();
@@ -391,7 +775,7 @@ mod test {
     fn test_finned_jellfish() {
         let s = "...16.87..1.875..38.73..651.5.62173...17..5.473.5..1...7........8.256917.62..7...";
         let grid = Grid::new_from_singline_digit(s).unwrap();
-        let finder = FishFinder::new(FishType::FinnedJellFish);
+        let finder = FishFinder::new(FishType::Finned(4)).unwrap();
         let mut acc = AllStepAccumulator::default();
         finder.find_step(&grid, &mut acc);
         let steps = acc.get_steps();
@@ -401,7 +785,7 @@ mod test {
     fn test_sashimi_jellfish() {
         let s = "..34162..26...31.41.4....36.463715.2.2184......762.41...5.3..41..21.4...41.56732.";
         let grid = Grid::new_from_singline_digit(s).unwrap();
-        let finder = FishFinder::new(FishType::SashimiJellyFish);
+        let finder = FishFinder::new(FishType::Sashimi(4)).unwrap();
         let mut acc = AllStepAccumulator::default();
         finder.find_step(&grid, &mut acc);
         let steps = acc.get_steps();
@@ -411,7 +795,7 @@ mod test {
     fn test_basic_xwing() {
         let s = ":0300:5:.+4+1+7+2+9.+3.76+9..3+4.2.+3264.+7+194.39..+17.+6.+7..49.3+1+95+3+7..2+4+21+456+7+3+9+837+6.9.+541+9+5+8+4+3+1+26+7::545:r25 c58";
         let grid = Grid::new_from_hodoku_line(s).unwrap();
-        let finder = FishFinder::new(FishType::XWing);
+        let finder = FishFinder::new(FishType::Basic(2)).unwrap();
         let mut acc = AllStepAccumulator::default();
         finder.find_step(&grid, &mut acc);
         let steps = acc.get_steps();
@@ -422,7 +806,7 @@ mod test {
     fn test_sword_fish() {
         let s = ":0301:2:16.54+3.7..+78+6.1+43+5+43+58.+7+6.+17+2.+45+8.696..9+12.57...+3+7+6..+4.+1+6.3..4.+3...+8..16..+71645.+3::268 271:r239 c158";
         let grid = Grid::new_from_hodoku_line(s).unwrap();
-        let finder = FishFinder::new(FishType::SwordFish);
+        let finder = FishFinder::new(FishType::Basic(3)).unwrap();
         let mut acc = AllStepAccumulator::default();
         finder.find_step(&grid, &mut acc);
         let steps = acc.get_steps();
@@ -433,10 +817,62 @@ mod test {
     fn test_jell_fish() {
         let s = ":0302:7:2.......3.8..3..5...34.21....12.54......9......93.86....25.69...9..2..7.4.......1::712 715 721 729 751 752 759 792 795:r3467 c1259";
         let grid = Grid::new_from_hodoku_line(s).unwrap();
-        let finder = FishFinder::new(FishType::JellyFish);
+        let finder = FishFinder::new(FishType::Basic(4)).unwrap();
         let mut acc = AllStepAccumulator::default();
         finder.find_step(&grid, &mut acc);
         let steps = acc.get_steps();
         assert_eq!(steps.len(), 2);
     }
+
+    #[test]
+    fn test_franken_mutant_fish() {
+        let s = "..34162..26...31.41.4....36.463715.2.2184......762.41...5.3..41..21.4...41.56732.";
+        let grid = Grid::new_from_singline_digit(s).unwrap();
+        let solution = *BruteForceSolver::new().solve(&grid).values();
+        for (fish_type, count) in [(FishType::Franken(2), 32), (FishType::Mutant(2), 28)] {
+            let finder = FishFinder::new(fish_type).unwrap();
+            let mut acc = AllStepAccumulator::default();
+            finder.find_step(&grid, &mut acc);
+            let steps = acc.get_steps();
+            assert_eq!(steps.len(), count);
+            for step in steps.iter() {
+                let mut g = grid.clone();
+                step.apply(&mut g);
+                assert!(g.check_grid_valid(&solution));
+            }
+        }
+    }
+
+    #[test]
+    fn test_fish_size() {
+        assert!(FishFinder::new(FishType::Basic(8)).is_err());
+        assert!(FishFinder::new(FishType::Mutant(1)).is_err());
+        assert!(FishFinder::new(FishType::Kraken(7)).is_ok());
+    }
+
+    #[test]
+    fn test_kraken_fish() {
+        let s = "..34162..26...31.41.4....36.463715.2.2184......762.41...5.3..41..21.4...41.56732.";
+        let grid = Grid::new_from_singline_digit(s).unwrap();
+        let finder = FishFinder::new(FishType::Kraken(2)).unwrap();
+        let mut acc = AllStepAccumulator::default();
+        finder.find_step(&grid, &mut acc);
+        let steps = acc.get_steps();
+        assert_eq!(steps.len(), 105);
+        let solution = *BruteForceSolver::new().solve(&grid).values();
+        for step in steps.iter() {
+            let mut g = grid.clone();
+            step.apply(&mut g);
+            assert!(g.check_grid_valid(&solution));
+            match step {
+                Step::Fish(fish) => {
+                    assert!(!fish.fins.is_empty());
+                    assert!(!fish.chains.is_empty());
+                }
+                _ => {
+                    assert!(false);
+                }
+            }
+        }
+    }
 }
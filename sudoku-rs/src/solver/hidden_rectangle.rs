@@ -14,6 +14,7 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HiddenRectangle {
     pub remove_candidates: Vec<Candidate>,
     pub highlight_candidates: Vec<Candidate>,
@@ -9,6 +9,7 @@ use crate::{
  * **/
 
 #[derive(Debug, Clone, PartialEq, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HiddenSet {
     pub degree: u8,
     pub remove_candidates: Vec<Candidate>,
@@ -7,6 +7,7 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HiddenSingle {
     pub candidate: Candidate,
     pub house: u8,
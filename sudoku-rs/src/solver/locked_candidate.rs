@@ -9,6 +9,7 @@ use crate::{
 };
 
 #[derive(Debug, Clone, PartialEq, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LockedCandidate {
     pub remove_candidates: Vec<Candidate>,
     pub highlight_candidates: Vec<Candidate>,
@@ -67,6 +68,7 @@ impl LockedCandidate {
 }
 
 #[derive(Debug, Clone, PartialEq, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LockedCandidateType {
     Pointing,
     Claiming,
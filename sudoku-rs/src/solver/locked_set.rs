@@ -0,0 +1,205 @@
+use crate::{
+    candidate::Candidate,
+    grid::Grid,
+    grid_constant::get_house_cell_set,
+    solver::{SolverStrategy, step::Step, step_accumulator::StepAccumulator},
+    util::{
+        create_permutations,
+        digitset::DigitSet,
+        format_step::{format_candidates_cells, format_candidates_values, format_house},
+    },
+};
+
+/**
+locked subset: a naked pair or triple in the intersection of a block and a row or column, its
+values can be removed from the other cells of both houses
+**/
+#[derive(Debug, Clone, PartialEq, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LockedSet {
+    pub degree: u8,
+    pub block: u8,
+    pub line: u8,
+    pub highlight_candidates: Vec<Candidate>,
+    pub remove_candidates: Vec<Candidate>,
+}
+
+impl LockedSet {
+    pub fn apply(&self, grid: &mut Grid) {
+        for cand in self.remove_candidates.iter() {
+            grid.remvoe_candidate(cand);
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self.degree {
+            2 => "Locked Pair",
+            3 => "Locked Triple",
+            _ => "",
+        }
+    }
+
+    pub fn difficulty(&self) -> u32 {
+        match self.degree {
+            2 => 40,
+            3 => 60,
+            _ => 0,
+        }
+    }
+
+    pub fn explain(&self) -> String {
+        format!(
+            "<h3>{}</h3><p>cells {} are in <b>{}</b> and <b>{}</b> and have candidates {}, {} can be removed from {}</p>",
+            self.name(),
+            format_candidates_cells(&self.highlight_candidates),
+            format_house(self.block),
+            format_house(self.line),
+            format_candidates_values(&self.highlight_candidates),
+            format_candidates_values(&self.remove_candidates),
+            format_candidates_cells(&self.remove_candidates)
+        )
+    }
+}
+
+pub struct LockedSetFinder {
+    degree: u8,
+}
+
+impl LockedSetFinder {
+    // the intersection of a block and a line has three cells
+    pub fn new(degree: u8) -> Self {
+        assert!(
+            (2..=3).contains(&degree),
+            "locked set degree {} is not 2 or 3",
+            degree
+        );
+        LockedSetFinder { degree }
+    }
+
+    fn create_locked_set(
+        &self,
+        grid: &Grid,
+        cells: &[u8],
+        values: &DigitSet,
+        block: u8,
+        line: u8,
+    ) -> Option<LockedSet> {
+        let mut remove_candidates = Vec::new();
+        let others = get_house_cell_set(block).union(&get_house_cell_set(line));
+        for cell in others.iter().filter(|c| !cells.contains(c)) {
+            for v in grid.get_cell_candidate(cell).intersect(values).iter() {
+                remove_candidates.push(Candidate::new(cell, v));
+            }
+        }
+        if remove_candidates.is_empty() {
+            return None;
+        }
+        let mut highlight_candidates = Vec::new();
+        for cell in cells.iter() {
+            for v in grid.get_cell_candidate(*cell).iter() {
+                highlight_candidates.push(Candidate::new(*cell, v));
+            }
+        }
+        Some(LockedSet {
+            degree: self.degree,
+            block,
+            line,
+            highlight_candidates,
+            remove_candidates,
+        })
+    }
+
+    pub fn find_locked_set(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        for block in 18..27 {
+            let block_cells = grid.house_empty_cells(block);
+            // the three rows and the three columns through the block
+            let row = (block - 18) / 3 * 3;
+            let col = 9 + (block - 18) % 3 * 3;
+            for line in (row..row + 3).chain(col..col + 3) {
+                let cells = block_cells.intersect(&grid.house_empty_cells(line));
+                if cells.count() < self.degree {
+                    continue;
+                }
+                for cells in create_permutations(cells.values(), self.degree) {
+                    let values = cells.iter().fold(DigitSet::new_empty(), |u, cell| {
+                        u.union(&grid.get_cell_candidate(*cell))
+                    });
+                    if values.count() != self.degree {
+                        continue;
+                    }
+                    let Some(step) = self.create_locked_set(grid, &cells, &values, block, line)
+                    else {
+                        continue;
+                    };
+                    if acc.add_step(Step::LockedSet(step)) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl SolverStrategy for LockedSetFinder {
+    fn find_step(&self, grid: &Grid, acc: &mut dyn StepAccumulator) {
+        self.find_locked_set(grid, acc);
+    }
+
+    fn name(&self) -> &str {
+        "LockedSetFinder"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        candidate::Candidate,
+        grid::Grid,
+        solver::{
+            SolverStrategy, locked_set::LockedSetFinder, step::Step,
+            step_accumulator::AllStepAccumulator,
+        },
+    };
+
+    #[test]
+    fn test_locked_set() {
+        let s = ".---------------.----------------.---------------.
+| 5   27   47   | 467   3    78  | 1    268   9  |
+| 1   279  479  | 4567  456  78  | 28   268   3  |
+| 6   3    8    | 9     2    1   | 4    7     5  |
+:---------------+----------------+---------------:
+| 4   8    2    | 56    56   3   | 7    9     1  |
+| 37  1    5679 | 4567  8    279 | 25   236   46 |
+| 37  679  5679 | 1     456  279 | 258  2368  46 |
+:---------------+----------------+---------------:
+| 9   67   67   | 2     1    5   | 3    4     8  |
+| 8   4    1    | 3     7    6   | 9    5     2  |
+| 2   5    3    | 8     9    4   | 6    1     7  |
+'---------------'----------------'---------------'";
+        let grid = Grid::new_from_matrix_str(s).unwrap();
+        let mut acc = AllStepAccumulator::default();
+        LockedSetFinder::new(2).find_step(&grid, &mut acc);
+        let steps = acc.get_steps();
+        // r1c6,r2c6 (7,8), r5c1,r6c1 (3,7), r4c4,r4c5 (5,6) and r5c9,r6c9 (4,6)
+        assert_eq!(steps.len(), 4);
+        let locked = steps
+            .iter()
+            .find_map(|step| match step {
+                Step::LockedSet(locked) if locked.block == 22 => Some(locked),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(locked.block, 22);
+        assert_eq!(locked.line, 3);
+        assert_eq!(
+            locked.remove_candidates,
+            vec![
+                Candidate::new(39, 5),
+                Candidate::new(39, 6),
+                Candidate::new(49, 5),
+                Candidate::new(49, 6),
+            ]
+        );
+        assert_eq!(locked.difficulty(), 40);
+    }
+}
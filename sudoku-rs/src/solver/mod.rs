@@ -1,14 +1,23 @@
 use crate::{
+    error::Result,
     grid::Grid,
     solution::SolutionPath,
-    solver::step_accumulator::{SingleStepAccumulator, StepAccumulator},
+    solver::step_accumulator::{AllStepAccumulator, SingleStepAccumulator, StepAccumulator},
 };
 
+pub mod aligned_exclusion;
+pub mod als;
 pub mod avoidable_rectangle_1;
 pub mod avoidable_rectangle_2;
+pub mod backdoor;
+pub mod bent_set;
+pub mod bitboard;
 pub mod brute_force;
 pub mod bug_plus_one;
 pub mod chain;
+pub mod coloring;
+pub mod config;
+pub mod deadly_pattern;
 pub mod empty_rectangle;
 pub mod fish;
 pub mod full_house;
@@ -16,6 +25,7 @@ pub mod hidden_rectangle;
 pub mod hidden_set;
 pub mod hidden_single;
 pub mod locked_candidate;
+pub mod locked_set;
 pub mod naked_set;
 pub mod naked_single;
 pub mod skyscraper;
@@ -38,60 +48,40 @@ pub trait SolverStrategy {
     fn name(&self) -> &str;
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepGroup {
+    pub name: String,
+    // the lowest difficulty of the steps
+    pub difficulty: u32,
+    pub steps: Vec<step::Step>,
+}
+
 pub struct SimpleSolver {
     strategies: Vec<Box<dyn SolverStrategy>>,
+    config: config::SolverConfig,
 }
 
 impl SimpleSolver {
     pub fn new() -> Self {
-        let strategies: Vec<Box<dyn SolverStrategy>> = vec![
-            Box::new(full_house::FullHouseFinder::default()),
-            Box::new(naked_single::NakedSingleFinder::default()),
-            Box::new(hidden_single::HiddenSingleFinder::default()),
-            Box::new(naked_set::NakedSetFinder::new(2)),
-            Box::new(naked_set::NakedSetFinder::new(3)),
-            Box::new(hidden_set::HiddenSetFinder::new(2)),
-            Box::new(hidden_set::HiddenSetFinder::new(3)),
-            Box::new(locked_candidate::LockedCandidateFinder::new(
-                locked_candidate::LockedCandidateType::Pointing,
-            )),
-            Box::new(locked_candidate::LockedCandidateFinder::new(
-                locked_candidate::LockedCandidateType::Claiming,
-            )),
-            Box::new(avoidable_rectangle_1::AvoidableRectangleType1Finder::default()),
-            Box::new(avoidable_rectangle_2::AvoidableRectangleType2Finder::default()),
-            Box::new(unique_1::Unique1Finder::default()),
-            Box::new(unique_2::Unique2Finder::default()),
-            Box::new(unique_3::Unique3Finder::default()),
-            Box::new(unique_4::Unique4Finder::default()),
-            Box::new(unique_5::Unique5Finder::default()),
-            Box::new(unique_6::Unique6Finder::default()),
-            Box::new(empty_rectangle::EmptyRectangleFinder::default()),
-            Box::new(hidden_rectangle::HiddenRectangleFinder::default()),
-            Box::new(bug_plus_one::BugPlusOneFinder::default()),
-            Box::new(skyscraper::SkyscraperFinder::default()),
-            Box::new(sue_de_coq::SueDeCoqFinder::default()),
-            Box::new(fish::FishFinder::new(fish::FishType::XWing)),
-            Box::new(chain::remote_pair::RemotePairFinder::default()),
-            Box::new(two_string_kit::TwoStringKitFinder::default()),
-            Box::new(fish::FishFinder::new(fish::FishType::SwordFish)),
-            Box::new(fish::FishFinder::new(fish::FishType::JellyFish)),
-            Box::new(xywing::XYWingFinder::default()),
-            Box::new(wwing::WWingFinder::default()),
-            Box::new(fish::FishFinder::new(fish::FishType::FinnedXWing)),
-            Box::new(fish::FishFinder::new(fish::FishType::SashimiXWing)),
-            Box::new(fish::FishFinder::new(fish::FishType::FinnedSowrdFish)),
-            Box::new(fish::FishFinder::new(fish::FishType::SashimiSwordFish)),
-            Box::new(fish::FishFinder::new(fish::FishType::FinnedJellFish)),
-            Box::new(fish::FishFinder::new(fish::FishType::SashimiJellyFish)),
-            Box::new(chain::x_chain::XChainFinder::default()),
-            Box::new(chain::xy_chain::XYChainFinder::default()),
-            Box::new(chain::discontinuous_nice_loop::DiscontinuousNiceLoopFinder::default()),
-            Box::new(chain::continuous_nice_loop::ContinuousNiceLoopFinder::default()),
-            Box::new(chain::aic_type1::AicType1Finder::default()),
-            Box::new(chain::aic_type2::AicType2Finder::default()),
-        ];
-        Self { strategies }
+        Self::from_config(&config::SolverConfig::default()).unwrap()
+    }
+
+    pub fn from_config(config: &config::SolverConfig) -> Result<Self> {
+        Ok(Self {
+            strategies: config.create_strategies()?,
+            config: config.clone(),
+        })
+    }
+
+    pub fn config(&self) -> &config::SolverConfig {
+        &self.config
+    }
+
+    // the score of the config or the default difficulty of the step
+    pub fn difficulty(&self, step: &step::Step) -> u32 {
+        self.config
+            .score(step.name())
+            .unwrap_or_else(|| step.difficulty())
     }
 
     pub fn hint(&self, grid: &Grid) -> step::Step {
@@ -106,6 +96,40 @@ impl SimpleSolver {
         step::Step::Nothing
     }
 
+    // every step of the enabled strategies, grouped by the step name. groups are sorted by
+    // difficulty, steps in a group by the number of eliminations
+    pub fn find_all_steps(&self, grid: &Grid) -> Vec<StepGroup> {
+        let mut acc = AllStepAccumulator::default();
+        for strategy in self.strategies.iter() {
+            strategy.find_step(grid, &mut acc);
+        }
+        let mut groups: Vec<StepGroup> = Vec::new();
+        for step in acc.ordered_steps() {
+            let difficulty = self.difficulty(step);
+            match groups.iter_mut().find(|g| g.name == step.name()) {
+                Some(group) => {
+                    group.difficulty = group.difficulty.min(difficulty);
+                    group.steps.push(step.clone());
+                }
+                None => groups.push(StepGroup {
+                    name: step.name().to_string(),
+                    difficulty,
+                    steps: vec![step.clone()],
+                }),
+            }
+        }
+        for group in groups.iter_mut() {
+            group.steps.sort_by_cached_key(|step| {
+                (
+                    std::cmp::Reverse(step.eliminations(grid)),
+                    self.difficulty(step),
+                )
+            });
+        }
+        groups.sort_by(|a, b| a.difficulty.cmp(&b.difficulty).then(a.name.cmp(&b.name)));
+        groups
+    }
+
     pub fn solve(&self, grid: &mut Grid) -> SolutionPath {
         let mut solve_steps = Vec::new();
         let mut total_score = 0;
@@ -126,7 +150,7 @@ impl SimpleSolver {
                     //println!("start solve: {:?}", grid.to_digit_line());
                     step.apply(grid);
                     //println!("after apply:{:?}", grid.to_digit_line());
-                    total_score += step.difficulty();
+                    total_score += self.difficulty(step);
                     changed = true;
                     solve_steps.push(step.to_owned());
                     break;
@@ -146,7 +170,10 @@ impl SimpleSolver {
 
 #[cfg(test)]
 mod test {
-    use crate::{grid::Grid, solver::SimpleSolver};
+    use crate::{
+        grid::Grid,
+        solver::{SimpleSolver, config::SolverConfig},
+    };
 
     #[test]
     pub fn test_simple_solver() {
@@ -169,4 +196,39 @@ mod test {
         let step = solver.hint(&mut grid);
         println!("Step:{:?}\n", step);
     }
+
+    #[test]
+    pub fn test_find_all_steps() {
+        let s = "1.....569492.561.8.561.924...964.8.1.64.1....218.356.4.4.5...169.5.614.2621.....5";
+        let grid = Grid::new_from_singline_digit(s).unwrap();
+        let mut config = SolverConfig::hodoku();
+        // chains and complex fish are too slow for a test
+        let first_slow = config
+            .strategies
+            .iter()
+            .position(|s| s.name == "Sue de Coq")
+            .unwrap();
+        for strategy in config.strategies[first_slow..].iter_mut() {
+            strategy.enabled = false;
+        }
+        let solver = SimpleSolver::from_config(&config).unwrap();
+        let groups = solver.find_all_steps(&grid);
+        assert!(
+            groups
+                .windows(2)
+                .all(|w| w[0].difficulty <= w[1].difficulty)
+        );
+        let x_wing = groups.iter().find(|g| g.name == "X-Wing").unwrap();
+        assert_eq!(x_wing.difficulty, 140);
+        for group in groups.iter() {
+            let eliminations: Vec<usize> = group
+                .steps
+                .iter()
+                .map(|step| step.eliminations(&grid))
+                .collect();
+            assert!(eliminations.windows(2).all(|w| w[0] >= w[1]));
+            assert!(group.steps.iter().all(|step| step.name() == group.name));
+        }
+        assert_eq!(groups, solver.find_all_steps(&grid));
+    }
 }
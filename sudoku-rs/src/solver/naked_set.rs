@@ -1,7 +1,6 @@
 use crate::{
     candidate::Candidate,
     grid::{Grid, HouseType},
-    grid_constant::{block, col, row},
     solver::{SolverStrategy, step::Step, step_accumulator::StepAccumulator},
     util::{
         create_permutations,
@@ -12,31 +11,21 @@ use crate::{
 use std::collections::HashSet;
 
 #[derive(Debug, Clone, PartialEq, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NakedSet {
     pub degree: u8,
     pub remove_candidates: Vec<Candidate>,
     pub highlight_candidates: Vec<Candidate>,
     pub house: u8,
-    pub locked_house: u8,
-    pub locked: bool,
 }
 
 impl NakedSet {
     pub fn difficulty(&self) -> u32 {
-        if self.locked {
-            match self.degree {
-                2 => 40,
-                3 => 60,
-                4 => 100,
-                _ => 0,
-            }
-        } else {
-            match self.degree {
-                2 => 60,
-                3 => 80,
-                4 => 120,
-                _ => 0,
-            }
+        match self.degree {
+            2 => 60,
+            3 => 80,
+            4 => 120,
+            _ => 0,
         }
     }
     pub fn apply(&self, grid: &mut Grid) {
@@ -45,19 +34,11 @@ impl NakedSet {
         }
     }
     pub fn name(&self) -> &str {
-        if self.locked {
-            match self.degree {
-                2 => "Locked Pair",
-                3 => "Locked Triple",
-                _ => "",
-            }
-        } else {
-            match self.degree {
-                2 => "Naked Pair",
-                3 => "Naked Triple",
-                4 => "Naked Quadruple",
-                _ => "",
-            }
+        match self.degree {
+            2 => "Naked Pair",
+            3 => "Naked Triple",
+            4 => "Naked Quadruple",
+            _ => "",
         }
     }
     fn format_values(&self) -> String {
@@ -80,33 +61,17 @@ impl NakedSet {
     }
 
     pub fn explain(&self) -> String {
-        if !self.locked {
-            format!(
-                "<h3>{}</h3> <p> cells {} are both in the house <b>{}</b> and have candidates <b>{}</b>.one of the cells has to be <b>{}</b>  the other see {} same time can't be {} in <b>{}</b></p>",
-                self.name(),
-                format_candidates_cells(self.highlight_candidates.as_slice()),
-                format_house(self.house),
-                self.format_values(),
-                self.format_values(),
-                format_candidates_cells(self.highlight_candidates.as_slice()),
-                self.format_values(),
-                format_house(self.house)
-            )
-        } else {
-            format!(
-                "<h3>{}</h3> <p> cells {} are both in the house <b>{}</b> and <b>{}</b> have candidates <b>{}</b>.one of the cells has to be <b>{}</b> ,  the other see {} same time can't be {} in <b>{}</b> and<b>{}</b></p>",
-                self.name(),
-                format_candidates_cells(self.highlight_candidates.as_slice()),
-                format_house(self.house),
-                format_house(self.locked_house),
-                self.format_values(),
-                self.format_values(),
-                format_candidates_cells(self.highlight_candidates.as_slice()),
-                self.format_values(),
-                format_house(self.house),
-                format_house(self.locked_house)
-            )
-        }
+        format!(
+            "<h3>{}</h3> <p> cells {} are both in the house <b>{}</b> and have candidates <b>{}</b>.one of the cells has to be <b>{}</b>  the other see {} same time can't be {} in <b>{}</b></p>",
+            self.name(),
+            format_candidates_cells(self.highlight_candidates.as_slice()),
+            format_house(self.house),
+            self.format_values(),
+            self.format_values(),
+            format_candidates_cells(self.highlight_candidates.as_slice()),
+            self.format_values(),
+            format_house(self.house)
+        )
     }
 }
 
@@ -152,32 +117,14 @@ impl NakedSetFinder {
     ) -> Option<NakedSet> {
         let mut highlight_candidates = Vec::new();
         let mut remove_candidates = Vec::new();
-        let blocks: HashSet<u8> = cells.iter().map(|cell| block(*cell)).collect();
-        let rows: HashSet<u8> = cells.iter().map(|cell| row(*cell)).collect();
-        let cols: HashSet<u8> = cells.iter().map(|cell| col(*cell)).collect();
-        let mut all_house = HashSet::new();
-        all_house.insert(house);
-        if blocks.len() == 1 {
-            all_house.insert(blocks.into_iter().next().unwrap());
-        }
-        if rows.len() == 1 {
-            all_house.insert(rows.into_iter().next().unwrap());
-        }
-        if cols.len() == 1 {
-            all_house.insert(cols.into_iter().next().unwrap());
-        }
-        let locked = if all_house.len() > 1 { true } else { false };
-        for house in all_house.iter() {
-            let empty_cells = grid.house_empty_cells(*house);
-            for cell in empty_cells.iter() {
-                if cells.contains(&cell) {
-                    continue;
-                }
-                let cell_candidate = grid.get_cell_candidate(cell);
-                for value in cell_candidate.intersect(&values).iter() {
-                    let candidate = Candidate::new(cell, value);
-                    remove_candidates.push(candidate);
-                }
+        // a subset locked in a block and a line is a LockedSet, only the house is used here
+        for cell in grid.house_empty_cells(house).iter() {
+            if cells.contains(&cell) {
+                continue;
+            }
+            let cell_candidate = grid.get_cell_candidate(cell);
+            for value in cell_candidate.intersect(&values).iter() {
+                remove_candidates.push(Candidate::new(cell, value));
             }
         }
         for cell in cells.iter() {
@@ -191,21 +138,11 @@ impl NakedSetFinder {
         if remove_candidates.is_empty() {
             return None;
         }
-        let mut locked_house = 28;
-        if locked {
-            for h in all_house.iter() {
-                if *h != house {
-                    locked_house = *h;
-                }
-            }
-        }
         let step = NakedSet {
             degree: self.degree,
             remove_candidates,
             highlight_candidates,
-            house: house,
-            locked_house: locked_house,
-            locked,
+            house,
         };
         Some(step)
     }
@@ -1,17 +1,37 @@
 use crate::{
     grid::Grid,
     solver::{
+        aligned_exclusion::AlignedExclusion,
+        als::{AlsStep, death_blossom::DeathBlossom},
         avoidable_rectangle_1::AvoidableRectangleType1,
-        avoidable_rectangle_2::AvoidableRectangleType2, bug_plus_one::BugPlusOne, chain::ChainStep,
-        empty_rectangle::EmptyRectangle, fish::Fish, full_house::FullHouse,
-        hidden_rectangle::HiddenRectangle, hidden_set::HiddenSet, hidden_single::HiddenSingle,
-        locked_candidate::LockedCandidate, naked_set::NakedSet, naked_single::NakedSingle,
-        skyscraper::Skyscraper, sue_de_coq::SueDeCoq, two_string_kit::TwoStringKit,
-        unique::UniqueStep, wwing::WWing, xywing::XYWing,
+        avoidable_rectangle_2::AvoidableRectangleType2,
+        bent_set::BentSet,
+        bug_plus_one::BugPlusOne,
+        chain::{ChainStep, forcing_chain::ForcingChainStep},
+        coloring::{Coloring, medusa::Medusa},
+        deadly_pattern::DeadlyPatternStep,
+        empty_rectangle::EmptyRectangle,
+        fish::Fish,
+        full_house::FullHouse,
+        hidden_rectangle::HiddenRectangle,
+        hidden_set::HiddenSet,
+        hidden_single::HiddenSingle,
+        locked_candidate::LockedCandidate,
+        locked_set::LockedSet,
+        naked_set::NakedSet,
+        naked_single::NakedSingle,
+        skyscraper::Skyscraper,
+        sue_de_coq::SueDeCoq,
+        two_string_kit::TwoStringKit,
+        unique::UniqueStep,
+        wwing::WWing,
+        xywing::XYWing,
     },
 };
 
 #[derive(Debug, PartialEq, Clone, Default, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "data"))]
 pub enum Step {
     #[default]
     Nothing,
@@ -20,6 +40,8 @@ pub enum Step {
     HiddenSingle(HiddenSingle),
     LockedCandidate(LockedCandidate),
     HiddenSet(HiddenSet),
+    LockedSet(LockedSet),
+    #[cfg_attr(feature = "serde", serde(rename = "NakedSet"))]
     NackedSet(NakedSet),
     Fish(Fish),
     Skyscraper(Skyscraper),
@@ -34,6 +56,14 @@ pub enum Step {
     WWing(WWing),
     SueDeCoq(SueDeCoq),
     Chain(ChainStep),
+    Als(AlsStep),
+    ForcingChain(ForcingChainStep),
+    Coloring(Coloring),
+    Medusa(Medusa),
+    BentSet(BentSet),
+    AlignedExclusion(AlignedExclusion),
+    DeathBlossom(DeathBlossom),
+    DeadlyPattern(DeadlyPatternStep),
 }
 
 impl Step {
@@ -54,6 +84,7 @@ impl Step {
             Step::HiddenSet(hs) => {
                 hs.apply(grid);
             }
+            Step::LockedSet(ls) => ls.apply(grid),
             Step::LockedCandidate(lc) => {
                 lc.apply(grid);
             }
@@ -69,6 +100,14 @@ impl Step {
             Step::XYWing(xyw) => xyw.apply(grid),
             Step::SueDeCoq(sdc) => sdc.apply(grid),
             Step::Chain(chain) => chain.apply(grid),
+            Step::Als(als) => als.apply(grid),
+            Step::ForcingChain(fc) => fc.apply(grid),
+            Step::Coloring(coloring) => coloring.apply(grid),
+            Step::Medusa(medusa) => medusa.apply(grid),
+            Step::BentSet(bs) => bs.apply(grid),
+            Step::AlignedExclusion(ae) => ae.apply(grid),
+            Step::DeadlyPattern(dp) => dp.apply(grid),
+            Step::DeathBlossom(db) => db.apply(grid),
             Step::HiddenRectangle(hr) => hr.apply(grid),
             Step::Nothing => {}
         }
@@ -79,6 +118,7 @@ impl Step {
             Step::NakedSingle(_) => 4,
             Step::HiddenSingle(_) => 14,
             Step::HiddenSet(hs) => hs.difficulty(),
+            Step::LockedSet(ls) => ls.difficulty(),
             Step::LockedCandidate(_) => 50,
             Step::NackedSet(ns) => ns.difficulty(),
             Step::Fish(fish) => fish.difficulty(),
@@ -94,6 +134,14 @@ impl Step {
             Step::WWing(_) => 150,
             Step::SueDeCoq(_) => 250,
             Step::Chain(chain) => chain.difficulty(),
+            Step::Als(als) => als.difficulty(),
+            Step::ForcingChain(fc) => fc.difficulty(),
+            Step::Coloring(coloring) => coloring.difficulty(),
+            Step::Medusa(medusa) => medusa.difficulty(),
+            Step::BentSet(bs) => bs.difficulty(),
+            Step::AlignedExclusion(ae) => ae.difficulty(),
+            Step::DeadlyPattern(dp) => dp.difficulty(),
+            Step::DeathBlossom(db) => db.difficulty(),
             Step::Nothing => 0,
         }
     }
@@ -103,6 +151,7 @@ impl Step {
             Step::NakedSingle(_) => "Naked Single",
             Step::HiddenSingle(_) => "Hidden Single",
             Step::HiddenSet(hs) => hs.name(),
+            Step::LockedSet(ls) => ls.name(),
             Step::LockedCandidate(_) => "Locked Candidate",
             Step::NackedSet(ns) => ns.name(),
             Step::Fish(fish) => fish.name(),
@@ -118,15 +167,38 @@ impl Step {
             Step::WWing(_) => "W-Wing",
             Step::SueDeCoq(_) => "Sue de Coq",
             Step::Chain(chain) => chain.name(),
+            Step::Als(als) => als.name(),
+            Step::ForcingChain(fc) => fc.name(),
+            Step::Coloring(coloring) => coloring.name(),
+            Step::Medusa(medusa) => medusa.name(),
+            Step::BentSet(bs) => bs.name(),
+            Step::AlignedExclusion(ae) => ae.name(),
+            Step::DeadlyPattern(dp) => dp.name(),
+            Step::DeathBlossom(db) => db.name(),
             Step::Nothing => "Nothing",
         }
     }
+    // candidates removed from the cells which are still empty after the step
+    pub fn eliminations(&self, grid: &Grid) -> usize {
+        let mut after = grid.clone();
+        self.apply(&mut after);
+        (0..81_u8)
+            .filter(|cell| after.get_value(*cell) == 0)
+            .map(|cell| {
+                grid.get_cell_candidate(cell)
+                    .difference(&after.get_cell_candidate(cell))
+                    .count() as usize
+            })
+            .sum()
+    }
+
     pub fn explain(&self) -> String {
         match self {
             Step::FullHouse(fh) => fh.explain(),
             Step::NakedSingle(ns) => ns.explain(),
             Step::HiddenSingle(hs) => hs.explain(),
             Step::HiddenSet(hs) => hs.explain(),
+            Step::LockedSet(ls) => ls.explain(),
             Step::LockedCandidate(lc) => lc.explain(),
             Step::NackedSet(ns) => ns.explain(),
             Step::Fish(fish) => fish.explain(),
@@ -142,6 +214,14 @@ impl Step {
             Step::WWing(_) => "WWing".to_string(),
             Step::SueDeCoq(sd) => sd.explain(),
             Step::Chain(chain) => chain.name().to_string(),
+            Step::Als(als) => als.explain(),
+            Step::ForcingChain(fc) => fc.explain(),
+            Step::Coloring(coloring) => coloring.explain(),
+            Step::Medusa(medusa) => medusa.explain(),
+            Step::BentSet(bs) => bs.explain(),
+            Step::AlignedExclusion(ae) => ae.explain(),
+            Step::DeadlyPattern(dp) => dp.explain(),
+            Step::DeathBlossom(db) => db.explain(),
             Step::Nothing => "Nothing".to_string(),
         }
     }
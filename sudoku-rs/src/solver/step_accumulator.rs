@@ -42,11 +42,15 @@ impl SingleStepAccumulator {
 #[derive(Default)]
 pub struct AllStepAccumulator {
     steps: HashSet<Step>,
+    // the steps in the order they are found
+    ordered_steps: Vec<Step>,
 }
 
 impl StepAccumulator for AllStepAccumulator {
     fn add_step(&mut self, step: Step) -> bool {
-        self.steps.insert(step);
+        if self.steps.insert(step.clone()) {
+            self.ordered_steps.push(step);
+        }
         return false;
     }
     fn is_finish(&self) -> bool {
@@ -58,4 +62,8 @@ impl AllStepAccumulator {
     pub fn get_steps(&self) -> &HashSet<Step> {
         &self.steps
     }
+
+    pub fn ordered_steps(&self) -> &[Step] {
+        &self.ordered_steps
+    }
 }
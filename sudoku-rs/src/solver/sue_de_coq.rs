@@ -12,6 +12,7 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SueDeCoq {
     pub remove_candidates: Vec<Candidate>,
     pub block_candidates: Vec<Candidate>,
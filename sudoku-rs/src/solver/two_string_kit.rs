@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use crate::{
     candidate::Candidate,
@@ -9,6 +9,7 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TwoStringKit {
     pub value: u8,
     pub remove_candidates: Vec<Candidate>,
@@ -41,7 +42,7 @@ impl TwoStringKitFinder {
                         continue;
                     }
 
-                    let mut block_cells_map: HashMap<u8, Vec<u8>> = HashMap::new();
+                    let mut block_cells_map: BTreeMap<u8, Vec<u8>> = BTreeMap::new();
                     for cell in row_cells.union(&col_cells).iter() {
                         let b = block(cell);
                         block_cells_map.entry(b).or_default().push(cell);
@@ -8,6 +8,7 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UniqueType {
     Type1,
     Type2,
@@ -18,6 +19,7 @@ pub enum UniqueType {
 }
 
 #[derive(Debug, PartialEq, Clone, Hash, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UniqueStep {
     pub unique_type: UniqueType,
     pub highlight_candidates: Vec<Candidate>,
@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct IndexSet(u128);
 
 // all index between 0, 80, every value take 1 bit , 0 take the first bit, so one value of 0 save
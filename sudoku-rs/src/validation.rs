@@ -0,0 +1,157 @@
+use crate::{
+    error::{Result, SudokuError},
+    generator::minimal,
+    grid::Grid,
+    grid_constant::get_house_cell_set,
+    io,
+    solution::SolutionState,
+    solver::bitboard::BitboardSolver,
+};
+
+// the cells of a house which have the same value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub house: u8,
+    pub value: u8,
+    pub cells: Vec<u8>,
+}
+
+/**
+what is wrong with a puzzle:
+- conflicts: the givens which are repeated in a house
+- empty_cells: the empty cells which have no candidate left
+- state: the puzzle has no, one or more than one solution
+- solutions: the solution when unique, two distinct solutions when not unique
+- minimal: the puzzle has no redundant clue, only checked when the solution is unique
+**/
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationReport {
+    pub conflicts: Vec<Conflict>,
+    pub empty_cells: Vec<u8>,
+    pub state: SolutionState,
+    pub solutions: Vec<[u8; 81]>,
+    pub minimal: Option<bool>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.state == SolutionState::Unique
+    }
+}
+
+pub fn validate_text(text: &str) -> Result<ValidationReport> {
+    validate(&io::read_values(text)?)
+}
+
+pub fn validate(values: &[u8]) -> Result<ValidationReport> {
+    if values.len() != 81 || values.iter().any(|v| *v > 9) {
+        return Err(SudokuError::InvalidInput(
+            "validation needs 81 values from 0 to 9".to_string(),
+        ));
+    }
+    let mut report = ValidationReport {
+        conflicts: find_conflicts(values),
+        empty_cells: vec![],
+        state: SolutionState::NoSolution,
+        solutions: vec![],
+        minimal: None,
+    };
+    if !report.conflicts.is_empty() {
+        return Ok(report);
+    }
+    let mut grid = Grid::default();
+    for (cell, value) in values.iter().enumerate() {
+        if *value != 0 {
+            grid.set_value(cell as u8, *value, true);
+        }
+    }
+    report.empty_cells = (0..81)
+        .filter(|cell| grid.get_value(*cell) == 0 && grid.get_cell_candidate(*cell).is_empty())
+        .collect();
+    if !report.empty_cells.is_empty() {
+        return Ok(report);
+    }
+
+    let solver = BitboardSolver::new();
+    let solution = solver.solve(&grid);
+    report.state = solution.state().clone();
+    match report.state {
+        SolutionState::NoSolution => {}
+        SolutionState::Unique => {
+            report.solutions.push(*solution.values());
+            report.minimal = Some(minimal::is_minimal(&grid)?);
+        }
+        SolutionState::MoreThanOne => {
+            report.solutions.push(*solution.values());
+            report.solutions.extend(solution.other_values());
+        }
+    }
+    Ok(report)
+}
+
+fn find_conflicts(values: &[u8]) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    for house in 0..27 {
+        for value in 1..=9 {
+            let cells: Vec<u8> = get_house_cell_set(house)
+                .iter()
+                .filter(|cell| values[*cell as usize] == value)
+                .collect();
+            if cells.len() > 1 {
+                conflicts.push(Conflict {
+                    house,
+                    value,
+                    cells,
+                });
+            }
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        solution::SolutionState,
+        validation::{Conflict, validate, validate_text},
+    };
+
+    #[test]
+    fn test_validate() {
+        let s = "...16.87..1.875..38.73..651.5.62173...17..5.473.5..1...7........8.256917.62..7...";
+        let report = validate_text(s).unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.solutions.len(), 1);
+        assert_eq!(report.minimal, Some(false));
+
+        // the same digit twice in the first row and the first block
+        let report = validate_text(&s.replacen('.', "1", 1)).unwrap();
+        assert!(!report.is_valid());
+        assert!(report.conflicts.contains(&Conflict {
+            house: 0,
+            value: 1,
+            cells: vec![0, 3]
+        }));
+        assert!(report.conflicts.contains(&Conflict {
+            house: 18,
+            value: 1,
+            cells: vec![0, 10]
+        }));
+
+        let mut values = vec![0; 81];
+        values[0] = 1;
+        let report = validate(&values).unwrap();
+        assert_eq!(report.state, SolutionState::MoreThanOne);
+        assert_eq!(report.solutions.len(), 2);
+        assert_ne!(report.solutions[0], report.solutions[1]);
+        assert_eq!(report.minimal, None);
+
+        // r1c9 can't be any digit
+        let mut values = vec![0; 81];
+        values[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        values[80] = 9;
+        let report = validate(&values).unwrap();
+        assert_eq!(report.empty_cells, vec![8]);
+        assert_eq!(report.state, SolutionState::NoSolution);
+    }
+}
@@ -0,0 +1,105 @@
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::IndexedRandom};
+use sudoku_rs::{
+    grid::Grid,
+    io::{self, Format},
+    render::{RenderStyle, Renderer},
+};
+
+const ALPHABET: [char; 20] = [
+    '0', '1', '5', '9', '.', '+', ':', '|', '-', ' ', '\n', '#', '[', 'x', 'a', 'é', '\t', '\'',
+    '\u{0}', '7',
+];
+
+// every parser must return an error instead of panic
+fn parse_all(text: &str) {
+    let _ = Grid::new_from_singline_digit(text);
+    let _ = Grid::new_from_hodoku_line(text);
+    let _ = Grid::new_from_matrix_str(text);
+    let _ = io::detect_format(text);
+    let _ = io::read(text);
+    for format in [
+        Format::Line,
+        Format::SimpleSudoku,
+        Format::SadMan,
+        Format::HoDoKu,
+        Format::Candidates,
+    ] {
+        if let Err(e) = io::read_as(text, format) {
+            assert!(!e.to_string().is_empty());
+        }
+    }
+}
+
+fn random_text(rng: &mut StdRng) -> String {
+    let len = rng.random_range(0..200);
+    (0..len).map(|_| *ALPHABET.choose(rng).unwrap()).collect()
+}
+
+// change, insert or delete some characters of a valid input
+fn mutate(text: &str, rng: &mut StdRng) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    for _ in 0..rng.random_range(1..4) {
+        let c = *ALPHABET.choose(rng).unwrap();
+        let i = rng.random_range(0..=chars.len());
+        match rng.random_range(0..3) {
+            0 if i < chars.len() => chars[i] = c,
+            1 if i < chars.len() => {
+                chars.remove(i);
+            }
+            _ => chars.insert(i, c),
+        }
+    }
+    chars.into_iter().collect()
+}
+
+#[test]
+fn test_parsers_never_panic() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let s = "...16.87..1.875..38.73..651.5.62173...17..5.473.5..1...7........8.256917.62..7...";
+    let grid = Grid::new_from_singline_digit(s).unwrap();
+    let mut samples = vec![
+        s.to_string(),
+        ":0800:2:+8..+36.+9....+9.1.863.+63.+89..+59+24+6+7+3+1+5+83+8+6+9+5+17+2457+182+4+3+9+6+4+3+2+1+9658+769+8+5+37......+24+8+63+9::226:".to_string(),
+        Renderer::new(RenderStyle::Plain).render(&grid),
+        Renderer::new(RenderStyle::Compact).render(&grid),
+    ];
+    for format in [
+        Format::SimpleSudoku,
+        Format::SadMan,
+        Format::HoDoKu,
+        Format::Candidates,
+    ] {
+        samples.push(io::write(&grid, format));
+    }
+    for sample in samples.iter() {
+        parse_all(sample);
+        for _ in 0..500 {
+            parse_all(&mutate(sample, &mut rng));
+        }
+    }
+    for _ in 0..2000 {
+        parse_all(&random_text(&mut rng));
+    }
+}
+
+#[test]
+fn test_parser_error_position() {
+    let s = "...16.87..1.875..38.73..651.5.62173...17..5.473.5..1...7....a...8.256917.62..7...";
+    let err = Grid::new_from_singline_digit(s).unwrap_err();
+    assert!(err.to_string().contains("line 1, column 61"));
+
+    let line = s.replace('a', ".");
+    let err = Grid::new_from_singline_digit(&line[..80]).unwrap_err();
+    assert!(err.to_string().contains("expect 81 cells, got 80"));
+
+    let err = Grid::new_from_hodoku_line(":0000:x:1+").unwrap_err();
+    assert!(err.to_string().contains("line 1, column 11"));
+
+    let grid = Grid::new_from_singline_digit(&line).unwrap();
+    let text = Renderer::new(RenderStyle::Pencilmark).render(&grid);
+    let mut lines: Vec<String> = text.lines().map(|l| l.to_string()).collect();
+    // the first cell of the second row
+    lines[2].replace_range(2..3, "x");
+    let err = Grid::new_from_matrix_str(&lines.join("\n")).unwrap_err();
+    assert!(err.to_string().contains("line 3, column 3"));
+}
@@ -1,12 +1,22 @@
+use rand::SeedableRng;
 use rand::rand_core::block;
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 
 use sudoku_rs::{
     candidate::Candidate,
-    generator::generate,
+    generator::{generate, symmetry::Symmetry},
     grid::{Difficulty, Grid},
+    session::{Mistake, Session},
     solution::SolutionState,
-    solver::{SimpleSolver, brute_force::BruteForceSolver, chain::link::InferenceType, step::Step},
+    solver::{
+        SimpleSolver, StepGroup,
+        brute_force::BruteForceSolver,
+        chain::link::{Chain, InferenceType, Node},
+        config::SolverConfig,
+        step::Step,
+    },
+    validation,
 };
 use web_sys::console;
 
@@ -47,6 +57,13 @@ static GREEN_CANDADITE_COLOR: u32 = 0x3fda65;
 static FIN_CANDIDATE_COLOR: u32 = 0x7fbbff;
 static PURPLE_CANDIDATE: u32 = 0xd8b2ff;
 static OTHER_CANDIDATE: u32 = 0xa6ede3;
+static ALS_CANDIDATE_COLORS: [u32; 4] = [0x7fbbff, 0xd8b2ff, 0xa6ede3, 0xffd966];
+// the two colors of every coloring cluster
+static CLUSTER_COLORS: [(u32, u32); 2] = [(0x7fbbff, 0xffd966), (0xd8b2ff, 0xa6ede3)];
+// a stem has at most nine candidates, so every petal of a death blossom has its own color
+static PETAL_COLORS: [u32; 9] = [
+    0x7fbbff, 0xd8b2ff, 0xa6ede3, 0xffd966, 0xffb27f, 0xb5e48c, 0xf4a6d7, 0x9fa8da, 0xd7ccc8,
+];
 
 impl FrontCandidate {
     pub fn new(cell: u8, value: u8, color: u32) -> Self {
@@ -63,11 +80,15 @@ pub enum EdgeType {
     Weak,
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+// from and to are the first candidate of a group node, the group fields have every candidate of
+// the group and are empty for a single candidate
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Edge {
     from: FrontCandidate,
     to: FrontCandidate,
     edge_type: EdgeType,
+    from_group: Vec<FrontCandidate>,
+    to_group: Vec<FrontCandidate>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
@@ -79,6 +100,44 @@ pub struct Hint {
     pub lines: Vec<Edge>,
     pub explain: String,
 }
+// the steps of one technique, like the "All steps" panel of HoDoKu
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HintGroup {
+    pub name: String,
+    pub difficulty: u32,
+    pub hints: Vec<Hint>,
+}
+
+// the steps of a group are sorted by eliminations, the player doesn't need thousands of them
+const MAX_GROUP_HINTS: usize = 20;
+
+fn new_hint_groups(groups: &[StepGroup]) -> Vec<HintGroup> {
+    groups
+        .iter()
+        .map(|group| HintGroup {
+            name: group.name.clone(),
+            difficulty: group.difficulty,
+            hints: group
+                .steps
+                .iter()
+                .take(MAX_GROUP_HINTS)
+                .map(Hint::new_from_step)
+                .collect(),
+        })
+        .collect()
+}
+
+// all the steps are searched on the wasm thread, so the strategies which take seconds are left out
+fn new_all_steps_solver() -> SimpleSolver {
+    SimpleSolver::from_config(&SolverConfig::rating()).unwrap()
+}
+
+fn parse_digits(text: &str) -> Option<Vec<u8>> {
+    text.chars()
+        .map(|c| c.to_digit(10).map(|d| d as u8))
+        .collect()
+}
+
 fn candidates_to_frontcandidates(cands: &[Candidate], color: u32) -> Vec<FrontCandidate> {
     cands
         .iter()
@@ -102,6 +161,34 @@ fn new_other_candidates(cands: &[Candidate]) -> Vec<FrontCandidate> {
     candidates_to_frontcandidates(cands, OTHER_CANDIDATE)
 }
 
+fn chain_to_edges(chain: &Chain) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    for inf in chain.inferences.iter() {
+        let from = FrontCandidate::new(inf.start.cell(), inf.start.value(), FIN_CANDIDATE_COLOR);
+        let to = FrontCandidate::new(inf.end.cell(), inf.end.value(), FIN_CANDIDATE_COLOR);
+        let edge_type = match inf.inference_type {
+            InferenceType::Strong => EdgeType::Strong,
+            InferenceType::Weak => EdgeType::Weak,
+        };
+        let edge = Edge {
+            from: from,
+            to,
+            edge_type,
+            from_group: group_to_frontcandidates(&inf.start),
+            to_group: group_to_frontcandidates(&inf.end),
+        };
+        edges.push(edge);
+    }
+    edges
+}
+
+fn group_to_frontcandidates(node: &Node) -> Vec<FrontCandidate> {
+    match node {
+        Node::Single(_) => Vec::new(),
+        Node::Group(_, _) => new_fin_candidates(&node.candidates()),
+    }
+}
+
 impl Hint {
     pub fn new_from_step(step: &Step) -> Self {
         let mut hint = Hint::default();
@@ -149,12 +236,22 @@ impl Hint {
                 hint.remove_candidates = new_remove_candidates(&ns.remove_candidates);
                 hint
             }
+            Step::LockedSet(ls) => {
+                hint.highlight_candidates = new_green_candidates(&ls.highlight_candidates);
+                hint.remove_candidates = new_remove_candidates(&ls.remove_candidates);
+                hint
+            }
             Step::Fish(fish) => {
                 hint.remove_candidates = new_remove_candidates(&fish.remove_candidates);
                 hint.highlight_candidates = new_green_candidates(&fish.highlight_candidates);
                 let fins_candidates = new_fin_candidates(&fish.fins);
                 hint.highlight_candidates
                     .extend_from_slice(&fins_candidates);
+                hint.highlight_candidates
+                    .extend_from_slice(&new_purple_candidates(&fish.endo_fins));
+                for chain in fish.chains.iter() {
+                    hint.lines.extend(chain_to_edges(chain));
+                }
                 hint
             }
             Step::Skyscraper(sky) => {
@@ -239,35 +336,103 @@ impl Hint {
             }
             Step::Chain(chain) => {
                 hint.remove_candidates = new_remove_candidates(&chain.remove_candidates);
-                let mut edges = Vec::new();
-                for inf in chain.chain.inferences.iter() {
-                    let from = FrontCandidate::new(
-                        inf.start.cell(),
-                        inf.start.value(),
-                        FIN_CANDIDATE_COLOR,
-                    );
-                    let to =
-                        FrontCandidate::new(inf.end.cell(), inf.end.value(), FIN_CANDIDATE_COLOR);
-                    let edge_type = match inf.inference_type {
-                        InferenceType::Strong => EdgeType::Strong,
-                        InferenceType::Weak => EdgeType::Weak,
-                    };
-                    let edge = Edge {
-                        from: from,
-                        to,
-                        edge_type,
+                hint.lines = chain_to_edges(&chain.chain);
+                for edge in hint.lines.iter() {
+                    hint.highlight_candidates
+                        .extend_from_slice(edge.to_group.as_slice());
+                }
+                hint
+            }
+            Step::ForcingChain(forcing) => {
+                hint.set_values = new_green_candidates(&forcing.set_values);
+                hint.remove_candidates = new_remove_candidates(&forcing.remove_candidates);
+                for chain in forcing.chains.iter() {
+                    hint.lines.extend(chain_to_edges(chain));
+                }
+                hint
+            }
+            Step::Coloring(coloring) => {
+                hint.remove_candidates = new_remove_candidates(&coloring.remove_candidates);
+                for (i, cluster) in coloring.clusters.iter().enumerate() {
+                    let (color1, color2) = CLUSTER_COLORS[i % CLUSTER_COLORS.len()];
+                    hint.highlight_candidates
+                        .extend(candidates_to_frontcandidates(&cluster.color1, color1));
+                    hint.highlight_candidates
+                        .extend(candidates_to_frontcandidates(&cluster.color2, color2));
+                }
+                hint
+            }
+            Step::Medusa(medusa) => {
+                hint.remove_candidates = new_remove_candidates(&medusa.remove_candidates);
+                hint.set_values = new_green_candidates(&medusa.set_values);
+                let (color1, color2) = CLUSTER_COLORS[0];
+                hint.highlight_candidates
+                    .extend(candidates_to_frontcandidates(&medusa.color1, color1));
+                hint.highlight_candidates
+                    .extend(candidates_to_frontcandidates(&medusa.color2, color2));
+                hint
+            }
+            Step::BentSet(bent) => {
+                hint.remove_candidates = new_remove_candidates(&bent.remove_candidates);
+                hint.highlight_candidates = new_green_candidates(&bent.pivot);
+                for cand in bent.pincers.iter() {
+                    let color = if cand.value() == bent.value {
+                        PURPLE_CANDIDATE
+                    } else {
+                        FIN_CANDIDATE_COLOR
                     };
-                    edges.push(edge);
+                    hint.highlight_candidates
+                        .push(FrontCandidate::new_from_candidate(cand, color));
+                }
+                hint
+            }
+            Step::Als(als) => {
+                hint.remove_candidates = new_remove_candidates(&als.remove_candidates);
+                for (i, a) in als.alses.iter().enumerate() {
+                    let color = ALS_CANDIDATE_COLORS[i % ALS_CANDIDATE_COLORS.len()];
+                    hint.highlight_candidates
+                        .extend_from_slice(&candidates_to_frontcandidates(&a.candidates, color));
+                }
+                hint
+            }
+            Step::DeathBlossom(blossom) => {
+                hint.remove_candidates = new_remove_candidates(&blossom.remove_candidates);
+                hint.highlight_candidates = new_green_candidates(&blossom.stem);
+                for (petal, color) in blossom.petals.iter().zip(PETAL_COLORS.iter()) {
+                    hint.highlight_candidates
+                        .extend(candidates_to_frontcandidates(&petal.als.candidates, *color));
                 }
-                hint.lines = edges;
+                hint
+            }
+            Step::AlignedExclusion(ae) => {
+                hint.remove_candidates = new_remove_candidates(&ae.remove_candidates);
+                hint.highlight_candidates = new_green_candidates(&ae.base_candidates);
+                for (i, a) in ae.alses.iter().enumerate() {
+                    let color = ALS_CANDIDATE_COLORS[i % ALS_CANDIDATE_COLORS.len()];
+                    hint.highlight_candidates
+                        .extend_from_slice(&candidates_to_frontcandidates(&a.candidates, color));
+                }
+                hint
+            }
+            Step::DeadlyPattern(dp) => {
+                hint.remove_candidates = new_remove_candidates(&dp.remove_candidates);
+                hint.highlight_candidates = new_green_candidates(&dp.pattern);
+                hint.highlight_candidates
+                    .extend_from_slice(&new_fin_candidates(&dp.guardians));
                 hint
             }
         }
     }
 }
 
+// the symmetry can be omitted by the caller, no symmetry is used then.
+// the same seed always generates the same sudoku, a random one is generated without seed
 #[wasm_bindgen]
-pub fn generate_sudoku(difficulty_level: String) -> Result<JsValue, JsValue> {
+pub fn generate_sudoku(
+    difficulty_level: String,
+    symmetry: Option<String>,
+    seed: Option<u64>,
+) -> Result<JsValue, JsValue> {
     let mut n = 0;
     let df = match difficulty_level.as_str() {
         "Easy" => Difficulty::Easy,
@@ -277,6 +442,24 @@ pub fn generate_sudoku(difficulty_level: String) -> Result<JsValue, JsValue> {
         "Extreme" => Difficulty::Extreme,
         _ => Difficulty::Easy,
     };
+    let symmetry = match symmetry.as_deref() {
+        Some("Vertical") => Symmetry::Verital,
+        Some("Horizontal") => Symmetry::Horizontal,
+        Some("Diagonal") => Symmetry::Diagonal,
+        Some("AntiDiagonal") => Symmetry::AntiDiagonal,
+        Some("BiDiagonal") => Symmetry::BiDiagonal,
+        Some("Orthogonal") => Symmetry::Orthogonal,
+        Some("Rotational180") => Symmetry::Rotational180,
+        Some("Rotational90") => Symmetry::Rotational90,
+        Some("Full") => Symmetry::Full,
+        _ => Symmetry::None,
+    };
+    let mut config = generate::GeneratorConfig::new(&df);
+    config.symmetry = symmetry;
+    let mut rng = match seed {
+        Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+        None => ChaCha8Rng::from_rng(&mut rand::rng()),
+    };
 
     loop {
         if n > 10 {
@@ -284,7 +467,7 @@ pub fn generate_sudoku(difficulty_level: String) -> Result<JsValue, JsValue> {
             return Err(err);
         }
 
-        if let Ok(generate_grid) = generate::generate_sudoku(&df) {
+        if let Ok(generate_grid) = generate::generate_with_rng(&config, &mut rng) {
             let grid = generate_grid.grid;
             let digits = grid.values().to_vec();
             let solutions = generate_grid.solution.to_vec();
@@ -311,85 +494,113 @@ pub fn generate_sudoku(difficulty_level: String) -> Result<JsValue, JsValue> {
         n += 1;
     }
 }
-fn create_grid_from_str(digits: &str) -> Result<Grid, SudokuError> {
-    let text = digits.trim();
-    let lines = text.lines();
-    let count = lines.count();
-    if count == 1 {
-        if text.find(":").is_some() {
-            return Grid::new_from_hodoku_line(text).map_err(|_e| SudokuError::InvalidInput);
-        }
-        return Grid::new_from_singline_digit(text).map_err(|_e| SudokuError::InvalidInput);
-    }
-    if count > 9 {
-        return Grid::new_from_matrix_str(text).map_err(|_e| SudokuError::InvalidInput);
-    }
-    Err(SudokuError::InvalidInput)
+// the parse error keeps the line and column of the input
+fn create_grid_from_str(digits: &str) -> Result<Grid, String> {
+    sudoku_rs::io::read(digits).map_err(|e| e.to_string())
 }
 
 #[wasm_bindgen]
 pub fn calc_pms(digits: &str) -> Vec<String> {
-    if let Ok(grid) = create_grid_from_str(digits) {
-        let mut res = Vec::new();
-        for cell in 0_u8..81 {
-            let v = grid.get_value(cell);
-            if v != 0 {
-                res.push("".to_string());
-            } else {
-                let pms = grid.get_cell_candidate(cell);
-                res.push(pms.iter().map(|v| v.to_string()).collect());
-            }
+    match create_grid_from_str(digits) {
+        Ok(grid) => grid_pms(&grid),
+        Err(e) => {
+            console::log_1(&e.into());
+            vec![]
         }
-        return res;
-    } else {
-        return vec![];
     }
 }
 
 #[wasm_bindgen]
 pub fn import_sudoku(text: &str) -> Result<JsValue, JsValue> {
-    if let Ok(grid) = create_grid_from_str(text) {
-        let mut pms = Vec::new();
-        let solver = BruteForceSolver::new();
-        let solution = solver.solve(&grid);
-        web_sys::console::log_1(&format!(",state:{:?}", solution.state()).into());
-        match solution.state() {
-            SolutionState::NoSolution => {
-                let err = serde_wasm_bindgen::to_value(&SudokuError::NotUniqueSolution).unwrap();
-                return Err(err);
-            }
-            SolutionState::MoreThanOne => {
-                let err = serde_wasm_bindgen::to_value(&SudokuError::NotUniqueSolution).unwrap();
-                return Err(err);
-            }
-            SolutionState::Unique => {
-                for cell in 0_u8..81 {
-                    let v = grid.get_value(cell);
-                    if v == 0 {
-                        let cands = grid.get_cell_candidate(cell);
-                        pms.push(cands.iter().map(|c| c.to_string()).collect());
-                    } else {
-                        pms.push("".to_string());
-                    }
-                }
-                let is_given: Vec<bool> = grid.values().iter().map(|v| v != &0).collect();
-                let sudoku_result = SudokuResult {
-                    digits: grid.values().to_vec(),
-                    pms: pms,
-                    solutions: solution.values().to_vec(),
-                    score: 0,
-                    is_given,
-                };
-                if let Ok(r) = serde_wasm_bindgen::to_value(&sudoku_result) {
-                    return Ok(r);
+    let grid = create_grid_from_str(text).map_err(|e| JsValue::from_str(&e))?;
+    let mut pms = Vec::new();
+    let solver = BruteForceSolver::new();
+    let solution = solver.solve(&grid);
+    web_sys::console::log_1(&format!(",state:{:?}", solution.state()).into());
+    match solution.state() {
+        SolutionState::NoSolution => {
+            let err = serde_wasm_bindgen::to_value(&SudokuError::NotUniqueSolution).unwrap();
+            return Err(err);
+        }
+        SolutionState::MoreThanOne => {
+            let err = serde_wasm_bindgen::to_value(&SudokuError::NotUniqueSolution).unwrap();
+            return Err(err);
+        }
+        SolutionState::Unique => {
+            for cell in 0_u8..81 {
+                let v = grid.get_value(cell);
+                if v == 0 {
+                    let cands = grid.get_cell_candidate(cell);
+                    pms.push(cands.iter().map(|c| c.to_string()).collect());
                 } else {
-                    let err = serde_wasm_bindgen::to_value(&SudokuError::InvalidInput).unwrap();
-                    return Err(err);
+                    pms.push("".to_string());
                 }
             }
+            let is_given: Vec<bool> = grid.values().iter().map(|v| v != &0).collect();
+            let sudoku_result = SudokuResult {
+                digits: grid.values().to_vec(),
+                pms: pms,
+                solutions: solution.values().to_vec(),
+                score: 0,
+                is_given,
+            };
+            if let Ok(r) = serde_wasm_bindgen::to_value(&sudoku_result) {
+                return Ok(r);
+            } else {
+                let err = serde_wasm_bindgen::to_value(&SudokuError::InvalidInput).unwrap();
+                return Err(err);
+            }
         }
     }
-    return Err(serde_wasm_bindgen::to_value(&SudokuError::InvalidInput).unwrap());
+}
+
+#[derive(Serialize)]
+pub struct ConflictResult {
+    house: u8,
+    value: u8,
+    cells: Vec<u8>,
+}
+
+#[derive(Serialize)]
+pub struct ValidationResult {
+    // the parse error with line and column
+    error: Option<String>,
+    conflicts: Vec<ConflictResult>,
+    empty_cells: Vec<u8>,
+    state: String,
+    solutions: Vec<Vec<u8>>,
+    minimal: Option<bool>,
+}
+
+#[wasm_bindgen]
+pub fn validate_sudoku(text: &str) -> Result<JsValue, JsValue> {
+    let result = match validation::validate_text(text) {
+        Ok(report) => ValidationResult {
+            error: None,
+            conflicts: report
+                .conflicts
+                .into_iter()
+                .map(|c| ConflictResult {
+                    house: c.house,
+                    value: c.value,
+                    cells: c.cells,
+                })
+                .collect(),
+            empty_cells: report.empty_cells,
+            state: format!("{:?}", report.state),
+            solutions: report.solutions.iter().map(|s| s.to_vec()).collect(),
+            minimal: report.minimal,
+        },
+        Err(e) => ValidationResult {
+            error: Some(e.to_string()),
+            conflicts: vec![],
+            empty_cells: vec![],
+            state: format!("{:?}", SolutionState::NoSolution),
+            solutions: vec![],
+            minimal: None,
+        },
+    };
+    Ok(serde_wasm_bindgen::to_value(&result)?)
 }
 
 #[derive(Serialize, Deserialize)]
@@ -400,27 +611,24 @@ pub struct BackTracingSolution {
 
 #[wasm_bindgen]
 pub fn solve_backtracing(digits: &str) -> Result<JsValue, JsValue> {
-    if let Ok(grid) = create_grid_from_str(digits) {
-        let solver = BruteForceSolver::new();
-        let solution = solver.solve(&grid);
-        match solution.state() {
-            SolutionState::NoSolution | SolutionState::MoreThanOne => {
-                let err = serde_wasm_bindgen::to_value(&SudokuError::NotUniqueSolution).unwrap();
-                return Err(err);
-            }
-            SolutionState::Unique => {
-                let back_tracing_solution = BackTracingSolution {
-                    count: 1,
-                    solutions: solution.values().to_vec(),
-                };
-
-                let res = serde_wasm_bindgen::to_value(&back_tracing_solution).unwrap();
-                return Ok(res);
-            }
+    let grid = create_grid_from_str(digits).map_err(|e| JsValue::from_str(&e))?;
+    let solver = BruteForceSolver::new();
+    let solution = solver.solve(&grid);
+    match solution.state() {
+        SolutionState::NoSolution | SolutionState::MoreThanOne => {
+            let err = serde_wasm_bindgen::to_value(&SudokuError::NotUniqueSolution).unwrap();
+            return Err(err);
+        }
+        SolutionState::Unique => {
+            let back_tracing_solution = BackTracingSolution {
+                count: 1,
+                solutions: solution.values().to_vec(),
+            };
+
+            let res = serde_wasm_bindgen::to_value(&back_tracing_solution).unwrap();
+            return Ok(res);
         }
     }
-    let err = Err(serde_wasm_bindgen::to_value(&SudokuError::InvalidInput).unwrap());
-    err
 }
 
 #[wasm_bindgen]
@@ -453,3 +661,138 @@ pub fn get_next_step(request: JsValue) -> Result<JsValue, JsValue> {
         return Err(err);
     }
 }
+
+// every step found in the grid of the request, grouped by technique
+#[wasm_bindgen]
+pub fn get_all_steps(request: JsValue) -> Result<JsValue, JsValue> {
+    let hint_request: HintRequest = serde_wasm_bindgen::from_value(request)?;
+    let invalid = || serde_wasm_bindgen::to_value(&SudokuError::InvalidInput).unwrap();
+    let digits = parse_digits(&hint_request.digits).ok_or_else(invalid)?;
+    let pms: Vec<Vec<u8>> = hint_request
+        .pms
+        .iter()
+        .map(|pm| parse_digits(pm))
+        .collect::<Option<_>>()
+        .ok_or_else(invalid)?;
+    let grid = Grid::new_from_digit_and_pms(digits.as_slice(), pms, hint_request.is_given)
+        .map_err(|_| invalid())?;
+    let groups = new_all_steps_solver().find_all_steps(&grid);
+    serde_wasm_bindgen::to_value(&new_hint_groups(&groups)).map_err(|e| e.into())
+}
+
+fn grid_pms(grid: &Grid) -> Vec<String> {
+    let mut pms = Vec::new();
+    for cell in 0_u8..81 {
+        if grid.get_value(cell) != 0 {
+            pms.push("".to_string());
+        } else {
+            let cands = grid.get_cell_candidate(cell);
+            pms.push(cands.iter().map(|v| v.to_string()).collect());
+        }
+    }
+    pms
+}
+
+// a game keeps its grid and history in rust, the web player only sends the moves
+#[wasm_bindgen]
+pub struct GameSession {
+    session: Session,
+    solver: SimpleSolver,
+    all_steps_solver: SimpleSolver,
+}
+
+#[wasm_bindgen]
+impl GameSession {
+    #[wasm_bindgen(constructor)]
+    pub fn new(text: &str) -> Result<GameSession, JsValue> {
+        // the parse and session errors are both the sudoku_rs error
+        let to_js = |e: sudoku_rs::error::SudokuError| serde_wasm_bindgen::to_value(&e).unwrap();
+        let grid = sudoku_rs::io::read(text).map_err(to_js)?;
+        let session = Session::new(grid).map_err(to_js)?;
+        Ok(GameSession {
+            session,
+            solver: SimpleSolver::new(),
+            all_steps_solver: new_all_steps_solver(),
+        })
+    }
+
+    pub fn digits(&self) -> Vec<u8> {
+        self.session.grid().values().to_vec()
+    }
+
+    pub fn pms(&self) -> Vec<String> {
+        grid_pms(self.session.grid())
+    }
+
+    pub fn is_given(&self) -> Vec<u8> {
+        let is_given = self.session.grid().is_given();
+        is_given.iter().map(|g| *g as u8).collect()
+    }
+
+    pub fn solutions(&self) -> Vec<u8> {
+        self.session.solution().to_vec()
+    }
+
+    pub fn set_value(&mut self, cell: u8, value: u8) -> bool {
+        self.session.set_value(cell, value).is_ok()
+    }
+
+    pub fn remove_candidate(&mut self, cell: u8, value: u8) -> bool {
+        self.session
+            .remove_candidate(Candidate::new(cell, value))
+            .is_ok()
+    }
+
+    pub fn undo(&mut self) -> bool {
+        self.session.undo()
+    }
+
+    pub fn redo(&mut self) -> bool {
+        self.session.redo()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.session.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.session.can_redo()
+    }
+
+    pub fn hint(&self) -> Result<JsValue, JsValue> {
+        let step = self.session.hint(&self.solver);
+        let hint = Hint::new_from_step(&step);
+        serde_wasm_bindgen::to_value(&hint).map_err(|e| e.into())
+    }
+
+    // all the steps can be played now, grouped by technique
+    pub fn all_hints(&self) -> Result<JsValue, JsValue> {
+        let groups = self.all_steps_solver.find_all_steps(self.session.grid());
+        serde_wasm_bindgen::to_value(&new_hint_groups(&groups)).map_err(|e| e.into())
+    }
+
+    // play the next step of the solver
+    pub fn apply_hint(&mut self) -> bool {
+        let step = self.session.hint(&self.solver);
+        self.session.apply_step(step).is_ok()
+    }
+
+    // the wrong values and the removed solutions, in the remove color
+    pub fn mistakes(&self) -> Result<JsValue, JsValue> {
+        let mistakes: Vec<FrontCandidate> = self
+            .session
+            .mistakes()
+            .iter()
+            .map(|m| match m {
+                Mistake::WrongValue(cand) | Mistake::RemovedSolution(cand) => {
+                    FrontCandidate::new_from_candidate(cand, REMOVE_CANDIDATE_COLOR)
+                }
+            })
+            .collect();
+        serde_wasm_bindgen::to_value(&mistakes).map_err(|e| e.into())
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.session.is_solved()
+    }
+}
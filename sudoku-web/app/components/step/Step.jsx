@@ -185,7 +185,7 @@ export default function Step() {
       stepName === "AIC Type2" ||
       stepName === "X-Chain" ||
       stepName === "XY-Chain" ||
-      stepName === "DisContinuous Nice Loop" ||
+      stepName === "Discontinuous Nice Loop" ||
       stepName === "Continuous Nice Loop"
     ) {
       let chainStep = ChainStep(nextStep);